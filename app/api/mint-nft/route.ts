@@ -6,11 +6,13 @@ import {
   Horizon,
   rpc as SorobanRpc,
   scValToNative,
+  xdr,
 } from '@stellar/stellar-sdk';
 import { apiError } from '@/lib/api/response';
 import {
   addressToScVal,
   getContract,
+  getNativeAssetContractId,
   getSorobanRpc,
   isValidAddress,
 } from '@/lib/stellar/utils/soroban';
@@ -127,7 +129,12 @@ export async function POST(request: NextRequest) {
       throw error;
     }
 
-    const contractCall = contract.call('mint', addressToScVal(walletAddress));
+    const contractCall = contract.call(
+      'mint',
+      addressToScVal(walletAddress),
+      addressToScVal(getNativeAssetContractId(passphrase)),
+      xdr.ScVal.scvVoid() // no allowlist proof
+    );
     const baseFee = isMainnet ? '100000' : '100';
 
     const tx = new TransactionBuilder(account, {
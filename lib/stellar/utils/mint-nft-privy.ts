@@ -25,7 +25,12 @@ import {
   assembleTransaction,
   parseRawSimulation,
 } from '@stellar/stellar-sdk/rpc';
-import { getContract, getSorobanRpc, addressToScVal } from './soroban';
+import {
+  getContract,
+  getSorobanRpc,
+  addressToScVal,
+  getNativeAssetContractId,
+} from './soroban';
 import { getHorizonUrlForNetwork } from './network';
 
 /** Signs 32-byte payloads (auth preimage hash or transaction hash) with Privy. */
@@ -72,7 +77,12 @@ export async function mintNFTWithPrivySign(params: {
 
   const rpc = getSorobanRpc(networkPassphrase);
   const contract = getContract(contractId);
-  const contractCall = contract.call('mint', addressToScVal(signerAddress));
+  const contractCall = contract.call(
+    'mint',
+    addressToScVal(signerAddress),
+    addressToScVal(getNativeAssetContractId(networkPassphrase)),
+    xdr.ScVal.scvVoid() // no allowlist proof
+  );
 
   const isMainnet =
     networkPassphrase.includes('Public') ||
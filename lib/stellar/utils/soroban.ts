@@ -1,4 +1,5 @@
 import {
+  Asset,
   Contract,
   Networks,
   rpc as SorobanRpc,
@@ -146,6 +147,13 @@ export const addressToScVal = (address: string): xdr.ScVal => {
   }
 };
 
+/**
+ * Get the XLM native asset (SAC) contract address for a network.
+ * NFT mints pay with this token unless another accepted payment token is chosen.
+ */
+export const getNativeAssetContractId = (passphrase: string): string =>
+  Asset.native().contractId(passphrase);
+
 /**
  * Build a contract instance for interaction
  */
@@ -1229,10 +1237,16 @@ export const mintNFT = async (
   }
 
   // Build the contract call
-  const contractCall = contract.call('mint', addressToScVal(recipientAddress));
+  const contractCall = contract.call(
+    'mint',
+    addressToScVal(recipientAddress),
+    addressToScVal(getNativeAssetContractId(passphrase)),
+    xdr.ScVal.scvVoid() // no allowlist proof
+  );
 
   // Note: Payment is now handled within the contract itself.
-  // The contract's mint function automatically transfers 0.01 XLM from the recipient to the contract.
+  // The contract's mint function automatically transfers the XLM mint price (0.01 XLM by default)
+  // from the recipient to the contract.
   // The recipient address must authorize the transaction (sign it) for the payment to work.
 
   // Use higher fees for mainnet Soroban transactions
@@ -0,0 +1,258 @@
+#![no_std]
+//! Lock-and-release bridge for moving IRL between Stellar and Base.
+//!
+//! Bridging out locks `IRLToken` in this contract and emits a `bridge_out`
+//! event naming the destination EVM address. Bridging in releases locked
+//! `IRLToken` once at least `threshold` of the configured ed25519 signers have
+//! attested to the transfer. Each attestation nonce can be used only once.
+
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, Vec,
+};
+use stellar_access::ownable::{self as ownable, Ownable};
+use stellar_macros::only_owner;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// `IRLToken` contract address.
+    Token,
+    /// ed25519 public keys of the attestation signers.
+    Signers,
+    /// Number of signer signatures required to release.
+    Threshold,
+    /// Nonce of the next `bridge_out`.
+    OutNonce,
+    /// Set once an attestation nonce has been used to release.
+    Released(u64),
+}
+
+/// One signer's signature over a release attestation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerSignature {
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+/// Message signers attest to (ed25519, over the SHA-256 of its XDR) to release tokens.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseAttestation {
+    /// Network passphrase hash, so an attestation cannot be replayed on another network.
+    pub network_id: BytesN<32>,
+    /// This bridge contract.
+    pub bridge: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub nonce: u64,
+}
+
+/// Emitted when IRL is locked for delivery to an EVM address.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeOut {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub nonce: u64,
+    pub evm_recipient: BytesN<20>,
+    pub amount: i128,
+}
+
+/// Emitted when locked IRL is released against an attestation.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Released {
+    #[topic]
+    pub recipient: Address,
+    #[topic]
+    pub nonce: u64,
+    pub amount: i128,
+}
+
+/// Emitted when the owner replaces the signer set.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignersUpdated {
+    pub signers: Vec<BytesN<32>>,
+    pub threshold: u32,
+}
+
+#[contract]
+pub struct IRLBridge;
+
+#[contractimpl]
+impl IRLBridge {
+    /// Constructor to initialize the bridge
+    ///
+    /// # Arguments
+    /// * `owner` - The address that can change the signer set
+    /// * `irl_token` - `IRLToken` contract address
+    /// * `signers` - ed25519 public keys of the attestation signers (N)
+    /// * `threshold` - Signatures required to release (M), between 1 and N
+    pub fn __constructor(
+        e: &Env,
+        owner: Address,
+        irl_token: Address,
+        signers: Vec<BytesN<32>>,
+        threshold: u32,
+    ) {
+        ownable::set_owner(e, &owner);
+        e.storage().instance().set(&DataKey::Token, &irl_token);
+        Self::write_signers(e, signers, threshold);
+    }
+
+    /// Owner-only. Replaces the signer set and threshold.
+    #[only_owner]
+    pub fn set_signers(e: &Env, signers: Vec<BytesN<32>>, threshold: u32) {
+        Self::write_signers(e, signers, threshold);
+    }
+
+    /// Returns the signer public keys.
+    pub fn signers(e: &Env) -> Vec<BytesN<32>> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .expect("Signers not initialized")
+    }
+
+    /// Returns the number of signatures required to release.
+    pub fn threshold(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .expect("Threshold not initialized")
+    }
+
+    /// Returns the `IRLToken` contract address.
+    pub fn token(e: &Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .expect("Token address not initialized")
+    }
+
+    /// Returns true if `nonce` has already been used to release tokens.
+    pub fn is_released(e: &Env, nonce: u64) -> bool {
+        e.storage().persistent().has(&DataKey::Released(nonce))
+    }
+
+    /// Lock `amount` of IRL from `from` for delivery to `evm_recipient` on Base.
+    ///
+    /// The emitted amount is what the bridge actually received, which is less
+    /// than `amount` if `IRLToken` charges a transfer fee on it.
+    ///
+    /// # Returns
+    /// The bridge-out nonce (sequential, starting from 0), also emitted in the
+    /// `bridge_out` event for the EVM side to deduplicate.
+    pub fn bridge_out(e: &Env, from: Address, amount: i128, evm_recipient: BytesN<20>) -> u64 {
+        if amount <= 0 {
+            panic!("Amount must be positive, got: {}", amount);
+        }
+        from.require_auth();
+
+        let token = token::Client::new(e, &Self::token(e));
+        let bridge = e.current_contract_address();
+        let before = token.balance(&bridge);
+        token.transfer(&from, &bridge, &amount);
+        let amount = token.balance(&bridge) - before;
+
+        let nonce: u64 = e.storage().instance().get(&DataKey::OutNonce).unwrap_or(0);
+        e.storage().instance().set(&DataKey::OutNonce, &(nonce + 1));
+        BridgeOut {
+            from,
+            nonce,
+            evm_recipient,
+            amount,
+        }
+        .publish(e);
+        nonce
+    }
+
+    /// Release `amount` of locked IRL to `recipient`, given signatures from at
+    /// least `threshold` distinct signers over the matching `ReleaseAttestation`.
+    /// Anyone can submit; each `nonce` can be used only once.
+    pub fn release(
+        e: &Env,
+        recipient: Address,
+        amount: i128,
+        nonce: u64,
+        signatures: Vec<SignerSignature>,
+    ) {
+        if amount <= 0 {
+            panic!("Amount must be positive, got: {}", amount);
+        }
+        if Self::is_released(e, nonce) {
+            panic!("Attestation nonce {} already used", nonce);
+        }
+
+        let attestation = ReleaseAttestation {
+            network_id: e.ledger().network_id(),
+            bridge: e.current_contract_address(),
+            recipient: recipient.clone(),
+            amount,
+            nonce,
+        };
+        let digest: Bytes = e.crypto().sha256(&attestation.to_xdr(e)).to_bytes().into();
+
+        let signers = Self::signers(e);
+        let mut seen: Vec<BytesN<32>> = Vec::new(e);
+        for entry in signatures.iter() {
+            if !signers.contains(&entry.public_key) {
+                panic!("Signature from unknown signer");
+            }
+            if seen.contains(&entry.public_key) {
+                panic!("Duplicate signer");
+            }
+            e.crypto()
+                .ed25519_verify(&entry.public_key, &digest, &entry.signature);
+            seen.push_back(entry.public_key);
+        }
+        let threshold = Self::threshold(e);
+        if seen.len() < threshold {
+            panic!("Not enough signatures: {} of {}", seen.len(), threshold);
+        }
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::Released(nonce), &true);
+        token::Client::new(e, &Self::token(e)).transfer(
+            &e.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+        Released {
+            recipient,
+            nonce,
+            amount,
+        }
+        .publish(e);
+    }
+
+    /// Validate and store the signer set.
+    fn write_signers(e: &Env, signers: Vec<BytesN<32>>, threshold: u32) {
+        if threshold == 0 || threshold > signers.len() {
+            panic!(
+                "Threshold must be between 1 and {}, got: {}",
+                signers.len(),
+                threshold
+            );
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(&signer) != Some(i as u32) {
+                panic!("Duplicate signer");
+            }
+        }
+        e.storage().instance().set(&DataKey::Signers, &signers);
+        e.storage().instance().set(&DataKey::Threshold, &threshold);
+        SignersUpdated { signers, threshold }.publish(e);
+    }
+}
+
+#[contractimpl(contracttrait)]
+impl Ownable for IRLBridge {}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,207 @@
+//! Tests for the IRL bridge contract.
+
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger},
+    vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+use irl_token::{IRLToken, IRLTokenClient};
+
+use crate::{IRLBridge, IRLBridgeClient, ReleaseAttestation, SignerSignature};
+
+fn signing_keys() -> [SigningKey; 3] {
+    [
+        SigningKey::from_bytes(&[1u8; 32]),
+        SigningKey::from_bytes(&[2u8; 32]),
+        SigningKey::from_bytes(&[3u8; 32]),
+    ]
+}
+
+fn public_key(e: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(e, &key.verifying_key().to_bytes())
+}
+
+/// Deploys IRL and a 2-of-3 bridge; returns the bridge, token and token owner.
+fn setup<'a>(
+    e: &'a Env,
+    keys: &[SigningKey; 3],
+) -> (IRLBridgeClient<'a>, IRLTokenClient<'a>, Address) {
+    let owner = Address::generate(e);
+    let irl_id = e.register(IRLToken, (&owner, false));
+    let signers = vec![
+        e,
+        public_key(e, &keys[0]),
+        public_key(e, &keys[1]),
+        public_key(e, &keys[2]),
+    ];
+    let bridge_id = e.register(IRLBridge, (&owner, &irl_id, signers, 2u32));
+    (
+        IRLBridgeClient::new(e, &bridge_id),
+        IRLTokenClient::new(e, &irl_id),
+        owner,
+    )
+}
+
+fn sign(
+    e: &Env,
+    bridge: &Address,
+    recipient: &Address,
+    amount: i128,
+    nonce: u64,
+    keys: &[&SigningKey],
+) -> Vec<SignerSignature> {
+    let attestation = ReleaseAttestation {
+        network_id: e.ledger().network_id(),
+        bridge: bridge.clone(),
+        recipient: recipient.clone(),
+        amount,
+        nonce,
+    };
+    let digest = e.crypto().sha256(&attestation.to_xdr(e)).to_array();
+    let mut signatures = Vec::new(e);
+    for key in keys {
+        signatures.push_back(SignerSignature {
+            public_key: public_key(e, key),
+            signature: BytesN::from_array(e, &key.sign(&digest).to_bytes()),
+        });
+    }
+    signatures
+}
+
+#[test]
+fn test_bridge_out_locks_tokens() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let keys = signing_keys();
+    let (bridge, irl, owner) = setup(&e, &keys);
+    let evm_recipient = BytesN::from_array(&e, &[0xab; 20]);
+    let owner_balance = irl.balance(&owner);
+
+    assert_eq!(bridge.bridge_out(&owner, &500, &evm_recipient), 0);
+    assert_eq!(bridge.bridge_out(&owner, &250, &evm_recipient), 1);
+
+    assert_eq!(irl.balance(&bridge.address), 750);
+    assert_eq!(irl.balance(&owner), owner_balance - 750);
+}
+
+#[test]
+fn test_bridge_out_reports_amount_received_after_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let keys = signing_keys();
+    let (bridge, irl, owner) = setup(&e, &keys);
+    let player = Address::generate(&e);
+    let evm_recipient = BytesN::from_array(&e, &[0xab; 20]);
+    irl.set_fee_exempt(&owner, &true);
+    irl.transfer(&owner, &player, &10_000);
+    irl.set_transfer_fee(&100); // 1%
+
+    bridge.bridge_out(&player, &10_000, &evm_recipient);
+
+    let (_, _, data) = e.events().all().last().unwrap();
+    let data = Map::<Symbol, Val>::try_from_val(&e, &data).unwrap();
+    let amount = i128::try_from_val(&e, &data.get(symbol_short!("amount")).unwrap()).unwrap();
+    assert_eq!(amount, 9_900);
+    assert_eq!(irl.balance(&bridge.address), 9_900);
+}
+
+#[test]
+fn test_release_with_threshold_signatures() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let keys = signing_keys();
+    let (bridge, irl, owner) = setup(&e, &keys);
+    let player = Address::generate(&e);
+    bridge.bridge_out(&owner, &1_000, &BytesN::from_array(&e, &[0xab; 20]));
+
+    let signatures = sign(&e, &bridge.address, &player, 400, 7, &[&keys[0], &keys[2]]);
+    bridge.release(&player, &400, &7, &signatures);
+
+    assert_eq!(irl.balance(&player), 400);
+    assert_eq!(irl.balance(&bridge.address), 600);
+    assert!(bridge.is_released(&7));
+
+    // The same attestation cannot be used twice.
+    assert!(bridge.try_release(&player, &400, &7, &signatures).is_err());
+}
+
+#[test]
+fn test_released_tokens_expire_again() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let keys = signing_keys();
+    let (bridge, irl, _owner) = setup(&e, &keys);
+    let player = Address::generate(&e);
+    let day: u64 = 24 * 60 * 60;
+    irl.set_lot_lifetime(&(30 * day));
+    irl.set_lot_exempt(&bridge.address, &true);
+    irl.mint(&player, &1_000);
+    bridge.bridge_out(&player, &1_000, &BytesN::from_array(&e, &[0xab; 20]));
+
+    // Locked tokens outlive their original expiry while the bridge holds them.
+    e.ledger().set_timestamp(40 * day);
+    assert_eq!(irl.balance(&bridge.address), 1_000);
+
+    let signatures = sign(
+        &e,
+        &bridge.address,
+        &player,
+        1_000,
+        1,
+        &[&keys[0], &keys[1]],
+    );
+    bridge.release(&player, &1_000, &1, &signatures);
+    assert_eq!(irl.lots(&player).get(0).unwrap().expires_at, 70 * day);
+
+    e.ledger().set_timestamp(70 * day);
+    assert_eq!(irl.balance(&player), 0);
+}
+
+#[test]
+fn test_release_rejects_too_few_or_invalid_signatures() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let keys = signing_keys();
+    let (bridge, irl, owner) = setup(&e, &keys);
+    let player = Address::generate(&e);
+    bridge.bridge_out(&owner, &1_000, &BytesN::from_array(&e, &[0xab; 20]));
+
+    let one = sign(&e, &bridge.address, &player, 400, 1, &[&keys[0]]);
+    assert!(bridge.try_release(&player, &400, &1, &one).is_err());
+
+    let duplicated = sign(&e, &bridge.address, &player, 400, 1, &[&keys[0], &keys[0]]);
+    assert!(bridge.try_release(&player, &400, &1, &duplicated).is_err());
+
+    let outsider = SigningKey::from_bytes(&[9u8; 32]);
+    let unknown = sign(&e, &bridge.address, &player, 400, 1, &[&keys[0], &outsider]);
+    assert!(bridge.try_release(&player, &400, &1, &unknown).is_err());
+
+    // Signatures over a different amount do not verify.
+    let wrong_amount = sign(&e, &bridge.address, &player, 400, 1, &[&keys[0], &keys[1]]);
+    assert!(bridge
+        .try_release(&player, &900, &1, &wrong_amount)
+        .is_err());
+
+    assert_eq!(irl.balance(&player), 0);
+    assert!(!bridge.is_released(&1));
+}
+
+#[test]
+fn test_set_signers_validates_threshold() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let keys = signing_keys();
+    let (bridge, _irl, _owner) = setup(&e, &keys);
+    let signers = vec![&e, public_key(&e, &keys[0]), public_key(&e, &keys[1])];
+
+    assert!(bridge.try_set_signers(&signers, &0).is_err());
+    assert!(bridge.try_set_signers(&signers, &3).is_err());
+
+    bridge.set_signers(&signers, &1);
+    assert_eq!(bridge.threshold(), 1);
+    assert_eq!(bridge.signers(), signers);
+}
@@ -0,0 +1,162 @@
+//! Epoch-based emission limits for minting.
+//!
+//! Once the owner sets a schedule, all mints (owner and minters alike) share a
+//! per-epoch allowance. Epochs are fixed windows of ledger time counted from
+//! when the schedule was set. The allowance can decay by a fixed share each
+//! epoch, and unused allowance only carries over when `rollover` is set.
+
+use soroban_sdk::{contracttype, Env};
+
+use crate::DataKey;
+
+/// Shortest allowed epoch. Catching up on elapsed epochs costs one step per
+/// epoch, so this keeps that work bounded (about 3,650 steps per decade).
+pub const MIN_EPOCH_LENGTH: u64 = 24 * 60 * 60;
+
+/// Emission policy set by the owner.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionSchedule {
+    /// Ledger timestamp at which epoch 0 starts.
+    pub start: u64,
+    /// Epoch length in seconds (e.g. `604800` for a week).
+    pub epoch_length: u64,
+    /// Maximum mintable in epoch 0, in smallest units.
+    pub epoch_emission: i128,
+    /// Share the allowance shrinks by each epoch, in basis points (0 = flat).
+    pub decay_bps: u32,
+    /// Whether unused allowance carries over into later epochs.
+    pub rollover: bool,
+}
+
+/// Minting progress in the most recent epoch that saw a mint (epoch 0 until
+/// the first mint under a schedule).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionState {
+    pub epoch: u64,
+    /// Allowance of `epoch` before any carry-over.
+    pub cap: i128,
+    pub minted: i128,
+    /// Unused allowance carried in from earlier epochs (rollover only).
+    pub carried: i128,
+}
+
+pub fn schedule(e: &Env) -> Option<EmissionSchedule> {
+    e.storage().instance().get(&DataKey::EmissionSchedule)
+}
+
+/// Replaces the schedule. Epoch 0 of the new schedule starts out having used
+/// whatever was minted in the old schedule's current epoch, so re-setting a
+/// schedule cannot refill the allowance.
+pub fn set_schedule(e: &Env, schedule: &EmissionSchedule) {
+    if schedule.epoch_length < MIN_EPOCH_LENGTH {
+        panic!(
+            "Epoch length must be at least {} seconds, got: {}",
+            MIN_EPOCH_LENGTH, schedule.epoch_length
+        );
+    }
+    if schedule.epoch_emission < 0 {
+        panic!(
+            "Epoch emission must not be negative, got: {}",
+            schedule.epoch_emission
+        );
+    }
+    if schedule.decay_bps > 10_000 {
+        panic!(
+            "Decay must be at most 10000 bps, got: {}",
+            schedule.decay_bps
+        );
+    }
+    let minted = self::schedule(e)
+        .map(|old| state(e, &old).minted)
+        .unwrap_or(0);
+    e.storage()
+        .instance()
+        .set(&DataKey::EmissionSchedule, schedule);
+    let state = EmissionState {
+        epoch: 0,
+        cap: schedule.epoch_emission,
+        minted: minted.min(schedule.epoch_emission),
+        carried: 0,
+    };
+    e.storage().instance().set(&DataKey::EmissionState, &state);
+}
+
+pub fn clear_schedule(e: &Env) {
+    e.storage().instance().remove(&DataKey::EmissionSchedule);
+    e.storage().instance().remove(&DataKey::EmissionState);
+}
+
+/// Returns the index of the current epoch.
+pub fn current_epoch(e: &Env, schedule: &EmissionSchedule) -> u64 {
+    e.ledger().timestamp().saturating_sub(schedule.start) / schedule.epoch_length
+}
+
+/// Returns what is still mintable in the current epoch, or `None` without a schedule.
+pub fn remaining(e: &Env) -> Option<i128> {
+    let schedule = schedule(e)?;
+    let state = state(e, &schedule);
+    Some(state.cap + state.carried - state.minted)
+}
+
+/// Records a mint of `amount`, panicking if it exceeds the current epoch's allowance.
+pub fn consume(e: &Env, amount: i128) {
+    let Some(schedule) = schedule(e) else {
+        return;
+    };
+    let mut state = state(e, &schedule);
+    let remaining = state.cap + state.carried - state.minted;
+    if amount > remaining {
+        panic!(
+            "Epoch emission exceeded: requested {} but {} remaining",
+            amount, remaining
+        );
+    }
+    state.minted += amount;
+    e.storage().instance().set(&DataKey::EmissionState, &state);
+}
+
+fn decay(schedule: &EmissionSchedule, cap: i128) -> i128 {
+    cap * (10_000 - schedule.decay_bps as i128) / 10_000
+}
+
+/// Returns the emission state for the current epoch, carrying over unused
+/// allowance from the last recorded epoch when rollover is on. Catching up
+/// starts from the stored epoch and stops early once the cap has decayed to 0.
+fn state(e: &Env, schedule: &EmissionSchedule) -> EmissionState {
+    let epoch = current_epoch(e, schedule);
+    let stored = e
+        .storage()
+        .instance()
+        .get(&DataKey::EmissionState)
+        .unwrap_or(EmissionState {
+            epoch: 0,
+            cap: schedule.epoch_emission,
+            minted: 0,
+            carried: 0,
+        });
+    if stored.epoch == epoch {
+        return stored;
+    }
+    let mut cap = stored.cap;
+    let mut carried = stored.carried + cap - stored.minted;
+    let mut at = stored.epoch;
+    while at < epoch && cap > 0 {
+        if schedule.decay_bps == 0 {
+            carried += cap * (epoch - at - 1) as i128;
+            break;
+        }
+        cap = decay(schedule, cap);
+        at += 1;
+        if at < epoch {
+            carried += cap;
+        }
+    }
+    EmissionState {
+        epoch,
+        cap,
+        minted: 0,
+        carried: if schedule.rollover { carried } else { 0 },
+    }
+}
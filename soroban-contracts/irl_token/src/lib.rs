@@ -1,13 +1,25 @@
 #![no_std]
+// The client generated for `permit` repeats its argument list.
+#![allow(clippy::too_many_arguments)]
 //! IRL fungible token contract (OpenZeppelin Stellar Base).
 //! Name: IRL, Symbol: IRL, 7 decimals, initial supply: 1_000_000 tokens.
 
 use soroban_sdk::{
-    contract, contractimpl, token::TokenInterface, Address, Env, MuxedAddress, String,
+    contract, contractevent, contractimpl, contracttype,
+    token::TokenInterface,
+    xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env, MuxedAddress, String, Symbol, Vec,
 };
 use stellar_access::ownable::{self as ownable, Ownable};
 use stellar_macros::only_owner;
-use stellar_tokens::fungible::Base;
+use stellar_tokens::fungible::{burnable::emit_burn, emit_approve, emit_transfer, Base};
+
+mod emission;
+mod lots;
+mod votes;
+
+pub use emission::EmissionSchedule;
+pub use lots::Lot;
 
 /// Initial supply in display units (1 million tokens).
 const INITIAL_SUPPLY_DISPLAY: i128 = 1_000_000;
@@ -15,6 +27,223 @@ const INITIAL_SUPPLY_DISPLAY: i128 = 1_000_000;
 const DECIMALS: u32 = 7;
 /// Initial supply in smallest units: 1_000_000 * 10^7.
 const INITIAL_SUPPLY: i128 = INITIAL_SUPPLY_DISPLAY * 10i128.pow(DECIMALS);
+/// Maximum length of a `transfer_with_ref` reference, in bytes.
+const MAX_REFERENCE_LEN: u32 = 64;
+/// Upper bound for the transfer fee: 10% (1_000 basis points).
+const MAX_TRANSFER_FEE_BPS: u32 = 1_000;
+
+/// Storage keys for IRL-specific state (balances and metadata live in `Base`).
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Set when the owner has frozen an address (fraud investigation).
+    Frozen(Address),
+    /// Set for addresses allowed to call `minter_mint`.
+    Minter(Address),
+    /// Address that receives clawed-back tokens and transfer fees. Defaults to the owner.
+    Treasury,
+    /// Set once the owner permanently disables clawback.
+    ClawbackDisabled,
+    /// Next expected `permit` nonce for an owner.
+    PermitNonce(Address),
+    /// Set at deploy time when the token is a non-transferable points balance.
+    NonTransferable,
+    /// Address an account delegates its voting power to (see `votes`).
+    Delegate(Address),
+    /// How long minted tokens stay spendable, in seconds (0 = forever).
+    LotLifetime,
+    /// An account's expiring lots (see `lots`).
+    Lots(Address),
+    /// Set for custody addresses (e.g. the bridge) whose received tokens never expire.
+    LotExempt(Address),
+    /// Fee on `transfer` / `transfer_from`, in basis points (0 when unset).
+    TransferFeeBps,
+    /// Set for addresses (e.g. our own contracts) whose transfers pay no fee.
+    FeeExempt(Address),
+    /// Owner-configured emission policy (see `emission`).
+    EmissionSchedule,
+    /// Minting progress in the current emission epoch.
+    EmissionState,
+    /// Total ever minted to an account (only mints increase it).
+    LifetimeEarned(Address),
+    /// Number of perk redemption receipts issued (also the last receipt ID).
+    ReceiptCount,
+    /// A perk redemption receipt by ID.
+    Receipt(u64),
+    /// Number of voting checkpoints written for a delegate.
+    NumCheckpoints(Address),
+    /// A delegate's checkpoint at the given index.
+    Checkpoint(Address, u32),
+}
+
+/// Message an owner signs (ed25519, over the SHA-256 of its XDR) to authorize `permit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermitMessage {
+    /// Network passphrase hash, so a signature cannot be replayed on another network.
+    pub network_id: BytesN<32>,
+    /// This token contract.
+    pub token: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub nonce: u64,
+    /// Ledger timestamp after which the signature is no longer accepted.
+    pub deadline: u64,
+}
+
+/// Emitted when the owner adds or removes a minter.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterUpdated {
+    #[topic]
+    pub minter: Address,
+    pub enabled: bool,
+}
+
+/// Emitted when the owner freezes an address.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountFrozen {
+    #[topic]
+    pub account: Address,
+}
+
+/// Emitted when the owner lifts a freeze.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountUnfrozen {
+    #[topic]
+    pub account: Address,
+}
+
+/// Emitted with the standard `transfer` (or, in non-transferable mode,
+/// `burn`) event when the owner claws tokens back, to record why.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClawbackReason {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub reason: Symbol,
+    pub amount: i128,
+}
+
+/// Emitted once when clawback is permanently disabled.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClawbackDisabled {}
+
+/// Emitted when the owner changes the treasury address.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryUpdated {
+    pub treasury: Address,
+}
+
+/// Emitted by `mint_with_reason`, linking a mint to an off-chain points activity.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintReason {
+    #[topic]
+    pub to: Address,
+    #[topic]
+    pub reason: Symbol,
+    pub reference: BytesN<32>,
+    pub amount: i128,
+}
+
+/// Record of a perk redemption, kept on-chain so venue staff can verify claims.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    /// Perk ID (UUID from the `perks` table).
+    pub perk_id: String,
+    pub player: Address,
+    pub amount: i128,
+    /// Ledger sequence the redemption was recorded in.
+    pub ledger: u32,
+}
+
+/// Emitted when a player burns IRL to redeem a perk.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PerkRedeemed {
+    #[topic]
+    pub player: Address,
+    #[topic]
+    pub receipt_id: u64,
+    pub perk_id: String,
+    pub amount: i128,
+}
+
+/// Emitted with the standard `burn` event when an account's expired lots are
+/// burned, either by `sweep_expired` or when the account next sends, receives or
+/// delegates tokens.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LotsExpired {
+    #[topic]
+    pub account: Address,
+    pub amount: i128,
+}
+
+/// Emitted when an address is added to or removed from the lot-exempt list.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LotExemptionUpdated {
+    #[topic]
+    pub account: Address,
+    pub exempt: bool,
+}
+
+/// Emitted when the owner changes the transfer fee.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferFeeUpdated {
+    pub fee_bps: u32,
+}
+
+/// Emitted when an address is added to or removed from the fee-exempt list.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeExemptionUpdated {
+    #[topic]
+    pub account: Address,
+    pub exempt: bool,
+}
+
+/// Emitted for the fee leg of a transfer, alongside the standard `transfer` event.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferFeeCharged {
+    #[topic]
+    pub from: Address,
+    pub treasury: Address,
+    pub amount: i128,
+}
+
+/// Emitted by `transfer_with_ref`, tying a transfer to an off-chain spend
+/// session or invoice. `amount` is what `to` received; `fee` went to the treasury.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferReference {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub reference: Bytes,
+}
+
+/// Emitted when the owner sets or clears the emission schedule.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmissionScheduleUpdated {
+    pub schedule: Option<EmissionSchedule>,
+}
 
 #[contract]
 pub struct IRLToken;
@@ -26,7 +255,10 @@ impl IRLToken {
     ///
     /// # Arguments
     /// * `owner` - Address that will own the contract and receive the initial supply.
-    pub fn __constructor(e: &Env, owner: Address) {
+    /// * `non_transferable` - Deploys the token as a soulbound points balance: no
+    ///   initial supply, balances only change through owner mints and burns, and
+    ///   `transfer`, `transfer_from`, `approve` and `permit` fail. Cannot be changed later.
+    pub fn __constructor(e: &Env, owner: Address, non_transferable: bool) {
         Base::set_metadata(
             e,
             DECIMALS,
@@ -34,13 +266,656 @@ impl IRLToken {
             String::from_str(e, "IRL"),
         );
         ownable::set_owner(e, &owner);
-        Base::mint(e, &owner, INITIAL_SUPPLY);
+        if non_transferable {
+            e.storage().instance().set(&DataKey::NonTransferable, &true);
+        } else {
+            Base::mint(e, &owner, INITIAL_SUPPLY);
+            Self::after_update(e, None, Some(&owner), INITIAL_SUPPLY);
+        }
+    }
+
+    /// Returns false when the token was deployed as a non-transferable points balance.
+    pub fn is_transferable(e: &Env) -> bool {
+        !e.storage()
+            .instance()
+            .get(&DataKey::NonTransferable)
+            .unwrap_or(false)
     }
 
     /// Owner-only mint. Mints `amount` (in smallest units) to `to`.
     #[only_owner]
     pub fn mint(e: &Env, to: Address, amount: i128) {
-        Base::mint(e, &to, amount);
+        Self::issue(e, &to, amount);
+    }
+
+    /// Mint path for approved minter contracts (e.g. the classic-asset wrapper).
+    /// `minter` must have been added with `add_minter` and must authorize the call.
+    /// Minted tokens never expire, since minters back them with locked assets,
+    /// but they count against the emission schedule like owner mints.
+    pub fn minter_mint(e: &Env, minter: Address, to: Address, amount: i128) {
+        minter.require_auth();
+        if !Self::is_minter(e, minter) {
+            panic!("Caller is not a minter");
+        }
+        emission::consume(e, amount);
+        Self::mint_to(
+            e,
+            &to,
+            Lot {
+                amount,
+                expires_at: u64::MAX,
+            },
+        );
+    }
+
+    /// Owner-only. Allows `minter` to call `minter_mint`.
+    #[only_owner]
+    pub fn add_minter(e: &Env, minter: Address) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Minter(minter.clone()), &true);
+        MinterUpdated {
+            minter,
+            enabled: true,
+        }
+        .publish(e);
+    }
+
+    /// Owner-only. Revokes a minter added with `add_minter`.
+    #[only_owner]
+    pub fn remove_minter(e: &Env, minter: Address) {
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Minter(minter.clone()));
+        MinterUpdated {
+            minter,
+            enabled: false,
+        }
+        .publish(e);
+    }
+
+    /// Returns true if `account` may call `minter_mint`.
+    pub fn is_minter(e: &Env, account: Address) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Minter(account))
+            .unwrap_or(false)
+    }
+
+    /// Owner-only mint that records why the tokens were issued.
+    ///
+    /// # Arguments
+    /// * `to` - Recipient of the minted tokens.
+    /// * `amount` - Amount in smallest units.
+    /// * `reason` - Activity type, e.g. `checkin`, `perk_rfnd`, `challenge`.
+    /// * `reference` - Off-chain reference (e.g. hash of the `points_activities` row ID).
+    #[only_owner]
+    pub fn mint_with_reason(
+        e: &Env,
+        to: Address,
+        amount: i128,
+        reason: Symbol,
+        reference: BytesN<32>,
+    ) {
+        Self::issue(e, &to, amount);
+        MintReason {
+            to,
+            reason,
+            reference,
+            amount,
+        }
+        .publish(e);
+    }
+
+    /// Owner-only. Freezes `account` so it can neither send nor receive IRL.
+    #[only_owner]
+    pub fn freeze(e: &Env, account: Address) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Frozen(account.clone()), &true);
+        AccountFrozen { account }.publish(e);
+    }
+
+    /// Owner-only. Lifts a freeze placed with `freeze`.
+    #[only_owner]
+    pub fn unfreeze(e: &Env, account: Address) {
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Frozen(account.clone()));
+        AccountUnfrozen { account }.publish(e);
+    }
+
+    /// Returns true if `account` is currently frozen.
+    pub fn is_frozen(e: &Env, account: Address) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Frozen(account))
+            .unwrap_or(false)
+    }
+
+    /// Owner-only. Sets the address that receives clawed-back tokens and transfer fees.
+    #[only_owner]
+    pub fn set_treasury(e: &Env, treasury: Address) {
+        e.storage().instance().set(&DataKey::Treasury, &treasury);
+        TreasuryUpdated { treasury }.publish(e);
+    }
+
+    /// Returns the treasury address (the owner until `set_treasury` is called).
+    /// Receives clawed-back tokens and transfer fees.
+    pub fn treasury(e: &Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .unwrap_or_else(|| ownable::get_owner(e).expect("Contract has no owner"))
+    }
+
+    /// Owner-only. Moves `amount` from `from` back to the treasury without the
+    /// holder's authorization, e.g. when points are reversed after fraud review.
+    /// Works on frozen accounts. Emits the standard `transfer` event and a
+    /// `clawback_reason` event recording `reason`. In non-transferable mode the
+    /// amount is burned instead and a standard `burn` event is emitted.
+    #[only_owner]
+    pub fn clawback(e: &Env, from: Address, amount: i128, reason: Symbol) {
+        if !Self::clawback_enabled(e) {
+            panic!("Clawback has been disabled");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive, got: {}", amount);
+        }
+        // Points cannot move between holders, so reversed points are burned.
+        let treasury = Self::is_transferable(e).then(|| Self::treasury(e));
+        Base::update(e, Some(&from), treasury.as_ref(), amount);
+        Self::after_update(e, Some(&from), treasury.as_ref(), amount);
+        match &treasury {
+            Some(treasury) => emit_transfer(e, &from, treasury, None, amount),
+            None => emit_burn(e, &from, amount),
+        }
+        ClawbackReason {
+            from,
+            reason,
+            amount,
+        }
+        .publish(e);
+    }
+
+    /// Owner-only. Permanently turns off `clawback`. This cannot be undone.
+    #[only_owner]
+    pub fn disable_clawback(e: &Env) {
+        if !Self::clawback_enabled(e) {
+            panic!("Clawback already disabled");
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::ClawbackDisabled, &true);
+        ClawbackDisabled {}.publish(e);
+    }
+
+    /// Returns false once `disable_clawback` has been called.
+    pub fn clawback_enabled(e: &Env) -> bool {
+        !e.storage()
+            .instance()
+            .get(&DataKey::ClawbackDisabled)
+            .unwrap_or(false)
+    }
+
+    /// Sets an allowance from an owner-signed message, so a relayer can submit the
+    /// approval and pay the fee on the owner's behalf.
+    ///
+    /// # Arguments
+    /// * `owner` - Account granting the allowance. Must be the G-address of `public_key`.
+    /// * `public_key` - Owner's ed25519 public key.
+    /// * `spender` / `amount` / `expiration_ledger` - Same as `approve`.
+    /// * `nonce` - Must equal `permit_nonce(owner)`; incremented on success.
+    /// * `deadline` - Ledger timestamp after which the signature expires.
+    /// * `signature` - ed25519 signature over SHA-256 of the `PermitMessage` XDR.
+    pub fn permit(
+        e: &Env,
+        owner: Address,
+        public_key: BytesN<32>,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        deadline: u64,
+        signature: BytesN<64>,
+    ) {
+        Self::require_transferable(e);
+        if e.ledger().timestamp() > deadline {
+            panic!("Permit expired");
+        }
+        if Self::account_address(e, &public_key) != owner {
+            panic!("Public key does not match owner");
+        }
+        let expected_nonce = Self::permit_nonce(e, owner.clone());
+        if nonce != expected_nonce {
+            panic!("Invalid nonce: expected {}, got {}", expected_nonce, nonce);
+        }
+
+        let message = PermitMessage {
+            network_id: e.ledger().network_id(),
+            token: e.current_contract_address(),
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount,
+            expiration_ledger,
+            nonce,
+            deadline,
+        };
+        let digest: Bytes = e.crypto().sha256(&message.to_xdr(e)).to_bytes().into();
+        e.crypto().ed25519_verify(&public_key, &digest, &signature);
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::PermitNonce(owner.clone()), &(nonce + 1));
+
+        // Same as `Base::approve`, with the signature standing in for `require_auth`.
+        Base::set_allowance(e, &owner, &spender, amount, expiration_ledger);
+        emit_approve(e, &owner, &spender, amount, expiration_ledger);
+    }
+
+    /// Returns the nonce the next `permit` signed by `owner` must use.
+    pub fn permit_nonce(e: &Env, owner: Address) -> u64 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::PermitNonce(owner))
+            .unwrap_or(0)
+    }
+
+    /// Delegates `account`'s voting power to `delegatee` (pass `account` itself
+    /// to undo). Holders vote with their own balance until they delegate.
+    pub fn delegate(e: &Env, account: Address, delegatee: Address) {
+        account.require_auth();
+        Self::burn_expired(e, &account);
+        votes::delegate(e, &account, &delegatee, Base::balance(e, &account));
+    }
+
+    /// Returns the address `account` currently delegates its voting power to.
+    pub fn delegates(e: &Env, account: Address) -> Address {
+        votes::delegates(e, &account)
+    }
+
+    /// Returns the current voting power of `account`.
+    pub fn get_votes(e: &Env, account: Address) -> i128 {
+        votes::get_votes(e, &account)
+    }
+
+    /// Returns the voting power of `account` at the end of a past `ledger`.
+    /// Use the ledger a vote opened at so tokens moved mid-vote do not count twice.
+    pub fn get_past_votes(e: &Env, account: Address, ledger: u32) -> i128 {
+        votes::get_past_votes(e, &account, ledger)
+    }
+
+    /// Owner-only. Sets the fee on `transfer` and `transfer_from` in basis points
+    /// (max 1_000 = 10%). The fee is deducted from the amount sent and credited
+    /// to the treasury. Defaults to 0.
+    #[only_owner]
+    pub fn set_transfer_fee(e: &Env, fee_bps: u32) {
+        if fee_bps > MAX_TRANSFER_FEE_BPS {
+            panic!(
+                "Transfer fee too high: {} bps (max {})",
+                fee_bps, MAX_TRANSFER_FEE_BPS
+            );
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::TransferFeeBps, &fee_bps);
+        TransferFeeUpdated { fee_bps }.publish(e);
+    }
+
+    /// Returns the transfer fee in basis points.
+    pub fn transfer_fee(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::TransferFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Owner-only. Exempts `account` (e.g. our own contracts) from the transfer
+    /// fee when it sends or receives.
+    #[only_owner]
+    pub fn set_fee_exempt(e: &Env, account: Address, exempt: bool) {
+        let key = DataKey::FeeExempt(account.clone());
+        if exempt {
+            e.storage().persistent().set(&key, &true);
+        } else {
+            e.storage().persistent().remove(&key);
+        }
+        FeeExemptionUpdated { account, exempt }.publish(e);
+    }
+
+    /// Returns true if transfers from or to `account` pay no fee.
+    pub fn is_fee_exempt(e: &Env, account: Address) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::FeeExempt(account))
+            .unwrap_or(false)
+    }
+
+    /// Sends IRL from `from` to many recipients with a single signature, e.g. a
+    /// partner tipping attendees from its allocation. Each leg is a normal
+    /// transfer (fees, freezes and a standard `transfer` event apply per leg).
+    pub fn transfer_batch(e: &Env, from: Address, transfers: Vec<(MuxedAddress, i128)>) {
+        if transfers.is_empty() {
+            panic!("No transfers in batch");
+        }
+        from.require_auth();
+        for (to, amount) in transfers.iter() {
+            Self::transfer_leg(e, &from, &to, amount);
+        }
+    }
+
+    /// Same as `transfer`, plus a `transfer_reference` event carrying `reference`
+    /// (a spend session or invoice ID, at most 64 bytes) so the payment can be
+    /// matched to its session without guessing by amount and time. The transfer
+    /// fee applies as usual; the event reports the net amount and the fee.
+    pub fn transfer_with_ref(
+        e: &Env,
+        from: Address,
+        to: MuxedAddress,
+        amount: i128,
+        reference: Bytes,
+    ) {
+        if reference.len() > MAX_REFERENCE_LEN {
+            panic!(
+                "Reference too long: {} bytes (max {})",
+                reference.len(),
+                MAX_REFERENCE_LEN
+            );
+        }
+        from.require_auth();
+        let fee = Self::transfer_leg(e, &from, &to, amount);
+        TransferReference {
+            from,
+            to: to.address(),
+            amount: amount - fee,
+            fee,
+            reference,
+        }
+        .publish(e);
+    }
+
+    /// Burns `amount` from `from` to redeem `perk_id` and stores a receipt.
+    ///
+    /// # Returns
+    /// The receipt ID (sequential, starting from 1), queryable with `receipt`.
+    pub fn redeem(e: &Env, from: Address, perk_id: String, amount: i128) -> u64 {
+        if amount <= 0 {
+            panic!("Amount must be positive, got: {}", amount);
+        }
+        Self::require_not_frozen(e, &from);
+        Base::burn(e, &from, amount);
+        Self::after_update(e, Some(&from), None, amount);
+
+        let receipt_id: u64 = e
+            .storage()
+            .instance()
+            .get(&DataKey::ReceiptCount)
+            .unwrap_or(0)
+            + 1;
+        e.storage()
+            .instance()
+            .set(&DataKey::ReceiptCount, &receipt_id);
+        e.storage().persistent().set(
+            &DataKey::Receipt(receipt_id),
+            &Receipt {
+                perk_id: perk_id.clone(),
+                player: from.clone(),
+                amount,
+                ledger: e.ledger().sequence(),
+            },
+        );
+        PerkRedeemed {
+            player: from,
+            receipt_id,
+            perk_id,
+            amount,
+        }
+        .publish(e);
+        receipt_id
+    }
+
+    /// Returns the perk redemption receipt with ID `receipt_id`.
+    pub fn receipt(e: &Env, receipt_id: u64) -> Receipt {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Receipt(receipt_id))
+            .expect("Receipt not found")
+    }
+
+    /// Moves `amount` from `from` to `to` after `from` has authorized it and
+    /// returns the fee taken from it.
+    fn transfer_leg(e: &Env, from: &Address, to: &MuxedAddress, amount: i128) -> i128 {
+        Self::require_transferable(e);
+        let to_address = to.address();
+        Self::require_not_frozen(e, from);
+        Self::require_not_frozen(e, &to_address);
+        if amount < 0 {
+            panic!("Amount must not be negative, got: {}", amount);
+        }
+        let fee = Self::fee_for(e, from, &to_address, amount);
+        Base::update(e, Some(from), Some(&to_address), amount - fee);
+        emit_transfer(e, from, &to_address, to.id(), amount - fee);
+        Self::after_update(e, Some(from), Some(&to_address), amount - fee);
+        Self::collect_fee(e, from, fee);
+        fee
+    }
+
+    /// Returns the fee owed on a transfer of `amount` from `from` to `to`.
+    fn fee_for(e: &Env, from: &Address, to: &Address, amount: i128) -> i128 {
+        let fee_bps = Self::transfer_fee(e);
+        if fee_bps == 0
+            || amount <= 0
+            || Self::is_fee_exempt(e, from.clone())
+            || Self::is_fee_exempt(e, to.clone())
+        {
+            return 0;
+        }
+        amount * fee_bps as i128 / 10_000
+    }
+
+    /// Moves a transfer fee from `from` to the treasury.
+    fn collect_fee(e: &Env, from: &Address, fee: i128) {
+        if fee == 0 {
+            return;
+        }
+        let treasury = Self::treasury(e);
+        Base::update(e, Some(from), Some(&treasury), fee);
+        Self::after_update(e, Some(from), Some(&treasury), fee);
+        emit_transfer(e, from, &treasury, None, fee);
+        TransferFeeCharged {
+            from: from.clone(),
+            treasury,
+            amount: fee,
+        }
+        .publish(e);
+    }
+
+    /// Owner-only. Sets how long newly minted tokens stay spendable, in seconds
+    /// (e.g. `31536000` for 12 months). `0` means minted tokens never expire.
+    /// Tokens already minted keep their expiry.
+    #[only_owner]
+    pub fn set_lot_lifetime(e: &Env, seconds: u64) {
+        lots::set_lifetime(e, seconds);
+    }
+
+    /// Returns the lifetime applied to new mints, in seconds (0 = never expire).
+    pub fn lot_lifetime(e: &Env) -> u64 {
+        lots::lifetime(e)
+    }
+
+    /// Owner-only. Makes every lot `account` receives non-expiring, for custody
+    /// contracts such as the bridge that must not lose locked tokens to expiry.
+    /// Lots it already holds keep their expiry.
+    #[only_owner]
+    pub fn set_lot_exempt(e: &Env, account: Address, exempt: bool) {
+        lots::set_exempt(e, &account, exempt);
+        LotExemptionUpdated { account, exempt }.publish(e);
+    }
+
+    /// Returns true if lots received by `account` never expire.
+    pub fn is_lot_exempt(e: &Env, account: Address) -> bool {
+        lots::is_exempt(e, &account)
+    }
+
+    /// Returns `account`'s lots, oldest expiry first, including expired ones.
+    pub fn lots(e: &Env, account: Address) -> Vec<Lot> {
+        lots::lots(e, &account)
+    }
+
+    /// Owner-only. Burns `account`'s expired lots and returns the amount burned,
+    /// e.g. to strip idle accounts' lapsed balances of voting power.
+    #[only_owner]
+    pub fn sweep_expired(e: &Env, account: Address) -> i128 {
+        Self::burn_expired(e, &account)
+    }
+
+    /// Returns the total the owner ever minted to `account` (`mint`,
+    /// `mint_with_reason`). Transfers, spends and burns never lower it, so it can
+    /// back tier progression on earned points.
+    pub fn lifetime_earned(e: &Env, account: Address) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::LifetimeEarned(account))
+            .unwrap_or(0)
+    }
+
+    /// Owner-only. Limits total minting (owner and minters) per epoch of ledger
+    /// time. Replacing a schedule keeps what was minted in the current epoch.
+    ///
+    /// # Arguments
+    /// * `epoch_length` - Epoch length in seconds, at least one day, e.g. `604800`
+    ///   for weekly issuance. Epoch 0 starts now.
+    /// * `epoch_emission` - Maximum mintable in epoch 0, in smallest units.
+    /// * `decay_bps` - Share the allowance shrinks by each epoch (0 = flat).
+    /// * `rollover` - Whether unused allowance carries into later epochs.
+    #[only_owner]
+    pub fn set_emission_schedule(
+        e: &Env,
+        epoch_length: u64,
+        epoch_emission: i128,
+        decay_bps: u32,
+        rollover: bool,
+    ) {
+        let schedule = EmissionSchedule {
+            start: e.ledger().timestamp(),
+            epoch_length,
+            epoch_emission,
+            decay_bps,
+            rollover,
+        };
+        emission::set_schedule(e, &schedule);
+        EmissionScheduleUpdated {
+            schedule: Some(schedule),
+        }
+        .publish(e);
+    }
+
+    /// Owner-only. Removes the emission schedule, so minting is unlimited again.
+    #[only_owner]
+    pub fn clear_emission_schedule(e: &Env) {
+        emission::clear_schedule(e);
+        EmissionScheduleUpdated { schedule: None }.publish(e);
+    }
+
+    /// Returns the emission schedule, or `None` if minting is unlimited.
+    pub fn emission_schedule(e: &Env) -> Option<EmissionSchedule> {
+        emission::schedule(e)
+    }
+
+    /// Returns the current emission epoch index (0 without a schedule).
+    pub fn current_epoch(e: &Env) -> u64 {
+        emission::schedule(e)
+            .map(|schedule| emission::current_epoch(e, &schedule))
+            .unwrap_or(0)
+    }
+
+    /// Returns what can still be minted in the current epoch, or `None` if
+    /// minting is unlimited.
+    pub fn emission_remaining(e: &Env) -> Option<i128> {
+        emission::remaining(e)
+    }
+
+    /// Mints `lot` to `to` once the caller's minting rights have been checked.
+    fn mint_to(e: &Env, to: &Address, lot: Lot) {
+        let amount = lot.amount;
+        Self::require_not_frozen(e, to);
+        Base::mint(e, to, amount);
+        Self::burn_expired(e, to);
+        lots::receive(e, to, Vec::from_array(e, [lot]));
+        votes::transfer_voting_units(e, None, Some(to), amount);
+    }
+
+    /// Owner issuance of earned points: mints an expiring lot and counts it
+    /// toward `lifetime_earned`. Minter mints are backed, not earned, and skip this.
+    fn issue(e: &Env, to: &Address, amount: i128) {
+        emission::consume(e, amount);
+        Self::mint_to(e, to, lots::new_lot(e, amount));
+        let earned = Self::lifetime_earned(e, to.clone()) + amount;
+        e.storage()
+            .persistent()
+            .set(&DataKey::LifetimeEarned(to.clone()), &earned);
+    }
+
+    /// Runs after every balance change (`None` for mints and burns). Moves lots
+    /// (failing if `from` lacks unexpired tokens) and voting power.
+    fn after_update(e: &Env, from: Option<&Address>, to: Option<&Address>, amount: i128) {
+        let moved = match from {
+            Some(from) => {
+                Self::burn_expired(e, from);
+                let taken = lots::spend(e, from, amount);
+                // Exempt holders keep lots non-expiring, so tokens they send out
+                // start a fresh lifetime instead of staying permanent.
+                if amount > 0 && lots::is_exempt(e, from) {
+                    Vec::from_array(e, [lots::new_lot(e, amount)])
+                } else {
+                    taken
+                }
+            }
+            None => Vec::from_array(e, [lots::new_lot(e, amount)]),
+        };
+        if let Some(to) = to {
+            Self::burn_expired(e, to);
+            lots::receive(e, to, moved);
+        }
+        votes::transfer_voting_units(e, from, to, amount);
+    }
+
+    /// Burns `account`'s expired lots, removing them from supply and voting power.
+    fn burn_expired(e: &Env, account: &Address) -> i128 {
+        let amount = lots::remove_expired(e, account);
+        if amount > 0 {
+            Base::update(e, Some(account), None, amount);
+            votes::transfer_voting_units(e, Some(account), None, amount);
+            emit_burn(e, account, amount);
+            LotsExpired {
+                account: account.clone(),
+                amount,
+            }
+            .publish(e);
+        }
+        amount
+    }
+
+    /// Builds the account address (G...) for an ed25519 public key.
+    fn account_address(e: &Env, public_key: &BytesN<32>) -> Address {
+        // XDR header: ScVal::Address, ScAddress::Account, PublicKeyTypeEd25519.
+        let mut xdr = Bytes::from_slice(e, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+        xdr.append(&public_key.clone().into());
+        Address::from_xdr(e, &xdr).expect("Invalid public key")
+    }
+
+    /// Panics if the token was deployed as non-transferable.
+    fn require_transferable(e: &Env) {
+        if !Self::is_transferable(e) {
+            panic!("Token is non-transferable");
+        }
+    }
+
+    /// Panics if `account` is frozen.
+    fn require_not_frozen(e: &Env, account: &Address) {
+        if Self::is_frozen(e, account.clone()) {
+            panic!("Account is frozen");
+        }
     }
 }
 
@@ -51,27 +926,45 @@ impl TokenInterface for IRLToken {
     }
 
     fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        IRLToken::require_transferable(&e);
         Base::approve(&e, &from, &spender, amount, expiration_ledger);
     }
 
     fn balance(e: Env, id: Address) -> i128 {
-        Base::balance(&e, &id)
+        lots::balance(&e, &id)
     }
 
     fn transfer(e: Env, from: Address, to: MuxedAddress, amount: i128) {
-        Base::transfer(&e, &from, &to, amount);
+        from.require_auth();
+        IRLToken::transfer_leg(&e, &from, &to, amount);
     }
 
     fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
-        Base::transfer_from(&e, &spender, &from, &to, amount);
+        IRLToken::require_transferable(&e);
+        IRLToken::require_not_frozen(&e, &spender);
+        IRLToken::require_not_frozen(&e, &from);
+        IRLToken::require_not_frozen(&e, &to);
+        let fee = IRLToken::fee_for(&e, &from, &to, amount);
+        Base::transfer_from(&e, &spender, &from, &to, amount - fee);
+        IRLToken::after_update(&e, Some(&from), Some(&to), amount - fee);
+        if fee > 0 {
+            // The spender's allowance covers the full amount, fee included.
+            Base::spend_allowance(&e, &from, &spender, fee);
+            IRLToken::collect_fee(&e, &from, fee);
+        }
     }
 
     fn burn(e: Env, from: Address, amount: i128) {
+        IRLToken::require_not_frozen(&e, &from);
         Base::burn(&e, &from, amount);
+        IRLToken::after_update(&e, Some(&from), None, amount);
     }
 
     fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
+        IRLToken::require_not_frozen(&e, &spender);
+        IRLToken::require_not_frozen(&e, &from);
         Base::burn_from(&e, &spender, &from, amount);
+        IRLToken::after_update(&e, Some(&from), None, amount);
     }
 
     fn decimals(e: Env) -> u32 {
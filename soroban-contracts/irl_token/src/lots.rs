@@ -0,0 +1,172 @@
+//! Expiring token lots.
+//!
+//! Every account's balance is split into lots, each carrying the ledger
+//! timestamp at which it lapses (`u64::MAX` for tokens that never expire).
+//! Lots are kept sorted by expiry, spends consume the oldest unexpired lots
+//! first, and transferred tokens keep their original expiry, except that tokens
+//! sent by a lot-exempt account get a fresh lifetime. The raw `Base`
+//! balance always equals the sum of an account's lots; expired lots stay
+//! there until the account next sends or receives tokens, delegates, or is
+//! swept with `sweep_expired`, which burns them.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::DataKey;
+
+/// Expiries are rounded up to a whole day so an account holds at most one lot
+/// per day of lifetime.
+const EXPIRY_GRANULARITY: u64 = 24 * 60 * 60;
+
+/// An amount of tokens that lapses at `expires_at` (ledger timestamp).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lot {
+    pub amount: i128,
+    pub expires_at: u64,
+}
+
+/// Returns how long newly minted tokens stay spendable, in seconds (0 = forever).
+pub fn lifetime(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&DataKey::LotLifetime)
+        .unwrap_or(0)
+}
+
+pub fn set_lifetime(e: &Env, seconds: u64) {
+    e.storage().instance().set(&DataKey::LotLifetime, &seconds);
+}
+
+/// Returns true if lots received by `account` are credited as non-expiring.
+pub fn is_exempt(e: &Env, account: &Address) -> bool {
+    e.storage()
+        .persistent()
+        .get(&DataKey::LotExempt(account.clone()))
+        .unwrap_or(false)
+}
+
+pub fn set_exempt(e: &Env, account: &Address, exempt: bool) {
+    let key = DataKey::LotExempt(account.clone());
+    if exempt {
+        e.storage().persistent().set(&key, &true);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+/// Returns all lots held by `account`, oldest expiry first, including expired ones.
+pub fn lots(e: &Env, account: &Address) -> Vec<Lot> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Lots(account.clone()))
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+/// Returns the unexpired part of `account`'s balance.
+pub fn balance(e: &Env, account: &Address) -> i128 {
+    let now = e.ledger().timestamp();
+    lots(e, account)
+        .iter()
+        .filter(|lot| lot.expires_at > now)
+        .map(|lot| lot.amount)
+        .sum()
+}
+
+/// Builds the lot for a fresh mint of `amount`.
+pub fn new_lot(e: &Env, amount: i128) -> Lot {
+    let lifetime = lifetime(e);
+    let expires_at = if lifetime == 0 {
+        u64::MAX
+    } else {
+        let expires_at = e.ledger().timestamp().saturating_add(lifetime);
+        expires_at.div_ceil(EXPIRY_GRANULARITY) * EXPIRY_GRANULARITY
+    };
+    Lot { amount, expires_at }
+}
+
+/// Removes `amount` from `account`'s unexpired lots, oldest first, and returns
+/// the pieces taken so they can be credited to a recipient with the same expiry.
+pub fn spend(e: &Env, account: &Address, amount: i128) -> Vec<Lot> {
+    let now = e.ledger().timestamp();
+    let mut held = lots(e, account);
+    let mut taken = Vec::new(e);
+    let mut remaining = amount;
+    let mut i = 0;
+    while remaining > 0 && i < held.len() {
+        let mut lot = held.get_unchecked(i);
+        if lot.expires_at <= now {
+            i += 1;
+            continue;
+        }
+        let used = remaining.min(lot.amount);
+        remaining -= used;
+        taken.push_back(Lot {
+            amount: used,
+            expires_at: lot.expires_at,
+        });
+        if used == lot.amount {
+            held.remove(i);
+        } else {
+            lot.amount -= used;
+            held.set(i, lot);
+            i += 1;
+        }
+    }
+    if remaining > 0 {
+        panic!("Insufficient unexpired balance");
+    }
+    write(e, account, &held);
+    taken
+}
+
+/// Adds `incoming` lots to `account`, merging lots with the same expiry.
+/// Lot-exempt accounts receive them as non-expiring.
+pub fn receive(e: &Env, account: &Address, incoming: Vec<Lot>) {
+    let exempt = is_exempt(e, account);
+    let mut held = lots(e, account);
+    for mut lot in incoming.iter() {
+        if exempt {
+            lot.expires_at = u64::MAX;
+        }
+        let mut i = 0;
+        while i < held.len() && held.get_unchecked(i).expires_at < lot.expires_at {
+            i += 1;
+        }
+        match held.get(i) {
+            Some(mut existing) if existing.expires_at == lot.expires_at => {
+                existing.amount += lot.amount;
+                held.set(i, existing);
+            }
+            _ => held.insert(i, lot),
+        }
+    }
+    write(e, account, &held);
+}
+
+/// Drops `account`'s expired lots and returns their total, which the caller burns.
+pub fn remove_expired(e: &Env, account: &Address) -> i128 {
+    let now = e.ledger().timestamp();
+    let held = lots(e, account);
+    let mut kept = Vec::new(e);
+    let mut expired = 0;
+    for lot in held.iter() {
+        if lot.expires_at <= now {
+            expired += lot.amount;
+        } else {
+            kept.push_back(lot);
+        }
+    }
+    if expired > 0 {
+        write(e, account, &kept);
+    }
+    expired
+}
+
+fn write(e: &Env, account: &Address, lots: &Vec<Lot>) {
+    let key = DataKey::Lots(account.clone());
+    if lots.is_empty() {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, lots);
+    }
+}
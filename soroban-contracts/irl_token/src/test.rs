@@ -1,17 +1,27 @@
 //! Tests for IRL fungible token contract.
 
+use ed25519_dalek::{Signer, SigningKey};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, MuxedAddress as _},
-    Address, Env,
+    map, symbol_short,
+    testutils::{Address as _, Events as _, Ledger, MuxedAddress as _},
+    vec,
+    xdr::{self, ToXdr},
+    Address, Bytes, BytesN, Env, IntoVal, Map, MuxedAddress, Symbol, TryFromVal, Val,
 };
 
-use crate::{IRLToken, IRLTokenClient};
+use crate::{IRLToken, IRLTokenClient, PermitMessage};
 
-fn setup(e: &Env) -> (IRLTokenClient, Address) {
-    let contract_id = e.register_contract(None, IRLToken);
+fn setup(e: &Env) -> (IRLTokenClient<'_>, Address) {
+    let owner = Address::generate(e);
+    let contract_id = e.register(IRLToken, (&owner, false));
     let client = IRLTokenClient::new(e, &contract_id);
+    (client, owner)
+}
+
+fn setup_non_transferable(e: &Env) -> (IRLTokenClient<'_>, Address) {
     let owner = Address::generate(e);
-    client.constructor(&owner);
+    let contract_id = e.register(IRLToken, (&owner, true));
+    let client = IRLTokenClient::new(e, &contract_id);
     (client, owner)
 }
 
@@ -35,7 +45,7 @@ fn test_transfer() {
     e.mock_all_auths();
     let (client, owner) = setup(&e);
     let recipient = Address::generate(&e);
-    let to_muxed = soroban_sdk::MuxedAddress::new(recipient.clone(), 0);
+    let to_muxed = soroban_sdk::MuxedAddress::from(recipient.clone());
 
     let amount: i128 = 100 * 10i128.pow(7); // 100 tokens
     client.transfer(&owner, &to_muxed, &amount);
@@ -43,3 +53,784 @@ fn test_transfer() {
     assert_eq!(client.balance(&owner), 1_000_000 * 10i128.pow(7) - amount);
     assert_eq!(client.balance(&recipient), amount);
 }
+
+#[test]
+fn test_freeze_and_unfreeze() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let account = Address::generate(&e);
+
+    assert!(!client.is_frozen(&account));
+    client.freeze(&account);
+    assert!(client.is_frozen(&account));
+    client.unfreeze(&account);
+    assert!(!client.is_frozen(&account));
+
+    let amount: i128 = 10 * 10i128.pow(7);
+    client.transfer(&owner, &account, &amount);
+    assert_eq!(client.balance(&account), amount);
+}
+
+#[test]
+fn test_frozen_account_cannot_receive_or_send() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let account = Address::generate(&e);
+    let amount: i128 = 10 * 10i128.pow(7);
+
+    client.transfer(&owner, &account, &amount);
+    client.freeze(&account);
+
+    let to_frozen = soroban_sdk::MuxedAddress::from(account.clone());
+    assert!(client.try_transfer(&owner, &to_frozen, &amount).is_err());
+    let to_owner = soroban_sdk::MuxedAddress::from(owner.clone());
+    assert!(client.try_transfer(&account, &to_owner, &amount).is_err());
+    assert!(client.try_burn(&account, &amount).is_err());
+    assert!(client.try_mint(&account, &amount).is_err());
+    assert_eq!(client.balance(&account), amount);
+}
+
+#[test]
+fn test_clawback_moves_tokens_to_treasury() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let holder = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let amount: i128 = 50 * 10i128.pow(7);
+
+    client.transfer(&owner, &holder, &amount);
+    client.set_treasury(&treasury);
+    assert_eq!(client.treasury(), treasury);
+
+    // Clawback still works on a frozen account.
+    client.freeze(&holder);
+    client.clawback(&holder, &(20 * 10i128.pow(7)), &symbol_short!("fraud"));
+    let events = e.events().all();
+    let transfer = vec![
+        &e,
+        symbol_short!("transfer").into_val(&e),
+        holder.into_val(&e),
+        treasury.into_val(&e),
+    ];
+    let reason = vec![
+        &e,
+        Symbol::new(&e, "clawback_reason").into_val(&e),
+        holder.into_val(&e),
+        symbol_short!("fraud").into_val(&e),
+    ];
+    assert!(events.iter().any(|(_, topics, _)| topics == transfer));
+    assert!(events.iter().any(|(_, topics, _)| topics == reason));
+
+    assert_eq!(client.balance(&holder), 30 * 10i128.pow(7));
+    assert_eq!(client.balance(&treasury), 20 * 10i128.pow(7));
+}
+
+#[test]
+fn test_disable_clawback_is_permanent() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let holder = Address::generate(&e);
+    let amount: i128 = 10 * 10i128.pow(7);
+    client.transfer(&owner, &holder, &amount);
+
+    assert!(client.clawback_enabled());
+    client.disable_clawback();
+    assert!(!client.clawback_enabled());
+
+    assert!(client
+        .try_clawback(&holder, &amount, &symbol_short!("fraud"))
+        .is_err());
+    assert!(client.try_disable_clawback().is_err());
+    assert_eq!(client.balance(&holder), amount);
+}
+
+#[test]
+fn test_mint_with_reason() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let player = Address::generate(&e);
+    let reference = BytesN::from_array(&e, &[7u8; 32]);
+    let amount: i128 = 5 * 10i128.pow(7);
+
+    client.mint_with_reason(&player, &amount, &symbol_short!("checkin"), &reference);
+
+    let (contract, topics, data) = e.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(
+        topics,
+        vec![
+            &e,
+            Symbol::new(&e, "mint_reason").into_val(&e),
+            player.into_val(&e),
+            symbol_short!("checkin").into_val(&e),
+        ]
+    );
+    assert_eq!(
+        Map::<Symbol, Val>::try_from_val(&e, &data).unwrap(),
+        map![
+            &e,
+            (symbol_short!("amount"), amount.into_val(&e)),
+            (symbol_short!("reference"), reference.into_val(&e)),
+        ]
+    );
+    assert_eq!(client.balance(&player), amount);
+}
+
+/// Returns the account address for a test signing key.
+fn account_for(e: &Env, signer: &SigningKey) -> Address {
+    let sc_address = xdr::ScAddress::Account(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(
+        xdr::Uint256(signer.verifying_key().to_bytes()),
+    )));
+    Address::try_from_val(e, &sc_address).unwrap()
+}
+
+fn sign_permit(e: &Env, signer: &SigningKey, message: &PermitMessage) -> BytesN<64> {
+    let digest = e.crypto().sha256(&message.clone().to_xdr(e)).to_array();
+    BytesN::from_array(e, &signer.sign(&digest).to_bytes())
+}
+
+#[test]
+fn test_permit_sets_allowance_and_consumes_nonce() {
+    let e = Env::default();
+    let (client, _owner) = setup(&e);
+    let signer = SigningKey::from_bytes(&[1u8; 32]);
+    let holder = account_for(&e, &signer);
+    let public_key = BytesN::from_array(&e, &signer.verifying_key().to_bytes());
+    let spender = Address::generate(&e);
+    let amount: i128 = 25 * 10i128.pow(7);
+    let expiration_ledger = e.ledger().sequence() + 1000;
+    let deadline = e.ledger().timestamp() + 600;
+
+    let message = PermitMessage {
+        network_id: e.ledger().network_id(),
+        token: client.address.clone(),
+        owner: holder.clone(),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce: 0,
+        deadline,
+    };
+    let signature = sign_permit(&e, &signer, &message);
+
+    // No auths are mocked: the relayer submits on the holder's behalf.
+    client.permit(
+        &holder,
+        &public_key,
+        &spender,
+        &amount,
+        &expiration_ledger,
+        &0,
+        &deadline,
+        &signature,
+    );
+    assert_eq!(client.allowance(&holder, &spender), amount);
+    assert_eq!(client.permit_nonce(&holder), 1);
+
+    // Replaying the same signature fails on the nonce.
+    assert!(client
+        .try_permit(
+            &holder,
+            &public_key,
+            &spender,
+            &amount,
+            &expiration_ledger,
+            &0,
+            &deadline,
+            &signature,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_permit_rejects_expired_deadline() {
+    let e = Env::default();
+    let (client, _owner) = setup(&e);
+    let signer = SigningKey::from_bytes(&[2u8; 32]);
+    let holder = account_for(&e, &signer);
+    let public_key = BytesN::from_array(&e, &signer.verifying_key().to_bytes());
+    let spender = Address::generate(&e);
+    let expiration_ledger = e.ledger().sequence() + 1000;
+    let deadline = 100;
+
+    let message = PermitMessage {
+        network_id: e.ledger().network_id(),
+        token: client.address.clone(),
+        owner: holder.clone(),
+        spender: spender.clone(),
+        amount: 1,
+        expiration_ledger,
+        nonce: 0,
+        deadline,
+    };
+    let signature = sign_permit(&e, &signer, &message);
+    e.ledger().set_timestamp(deadline + 1);
+
+    assert!(client
+        .try_permit(
+            &holder,
+            &public_key,
+            &spender,
+            &1,
+            &expiration_ledger,
+            &0,
+            &deadline,
+            &signature,
+        )
+        .is_err());
+}
+
+#[test]
+fn test_votes_follow_balances_and_delegation() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let holder = Address::generate(&e);
+    let delegatee = Address::generate(&e);
+    let amount: i128 = 100 * 10i128.pow(7);
+
+    client.transfer(&owner, &holder, &amount);
+    assert_eq!(client.get_votes(&holder), amount);
+    assert_eq!(client.delegates(&holder), holder);
+
+    client.delegate(&holder, &delegatee);
+    assert_eq!(client.get_votes(&holder), 0);
+    assert_eq!(client.get_votes(&delegatee), amount);
+    assert_eq!(client.balance(&delegatee), 0);
+}
+
+#[test]
+fn test_past_votes_ignore_later_transfers() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let voter = Address::generate(&e);
+    let other_wallet = Address::generate(&e);
+    let amount: i128 = 100 * 10i128.pow(7);
+
+    e.ledger().set_sequence_number(10);
+    client.transfer(&owner, &voter, &amount);
+
+    // Vote snapshot taken at ledger 10; tokens move to another wallet at ledger 20.
+    e.ledger().set_sequence_number(20);
+    client.transfer(&voter, &other_wallet, &amount);
+    e.ledger().set_sequence_number(21);
+
+    assert_eq!(client.get_past_votes(&voter, &10), amount);
+    assert_eq!(client.get_past_votes(&other_wallet, &10), 0);
+    assert_eq!(client.get_past_votes(&voter, &20), 0);
+    assert_eq!(client.get_past_votes(&other_wallet, &20), amount);
+    assert_eq!(client.get_past_votes(&voter, &5), 0);
+    assert!(client.try_get_past_votes(&voter, &21).is_err());
+}
+
+#[test]
+fn test_non_transferable_mode_allows_only_mint_and_burn() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup_non_transferable(&e);
+    let player = Address::generate(&e);
+    let other = Address::generate(&e);
+    let amount: i128 = 10 * 10i128.pow(7);
+
+    assert!(!client.is_transferable());
+    assert_eq!(client.balance(&owner), 0);
+
+    client.mint(&player, &amount);
+    assert_eq!(client.balance(&player), amount);
+
+    let to_other = soroban_sdk::MuxedAddress::from(other.clone());
+    assert!(client.try_transfer(&player, &to_other, &1).is_err());
+    assert!(client.try_approve(&player, &other, &1, &1000).is_err());
+    assert!(client
+        .try_transfer_from(&other, &player, &other, &1)
+        .is_err());
+
+    client.burn(&player, &(4 * 10i128.pow(7)));
+    assert_eq!(client.balance(&player), 6 * 10i128.pow(7));
+
+    // Clawback burns rather than moving points to the treasury.
+    client.clawback(&player, &10i128.pow(7), &symbol_short!("fraud"));
+    let burn = vec![&e, symbol_short!("burn").into_val(&e), player.into_val(&e)];
+    assert!(e.events().all().iter().any(|(_, topics, _)| topics == burn));
+    assert_eq!(client.balance(&player), 5 * 10i128.pow(7));
+    assert_eq!(client.balance(&owner), 0);
+}
+
+#[test]
+fn test_expired_lots_leave_balance_and_are_swept() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let player = Address::generate(&e);
+    let day: u64 = 24 * 60 * 60;
+    let amount: i128 = 10 * 10i128.pow(7);
+
+    client.set_lot_lifetime(&(365 * day));
+    client.mint(&player, &amount);
+    assert_eq!(client.balance(&player), amount);
+
+    e.ledger().set_timestamp(366 * day);
+    assert_eq!(client.balance(&player), 0);
+    let to_owner = soroban_sdk::MuxedAddress::from(owner.clone());
+    assert!(client.try_transfer(&player, &to_owner, &1).is_err());
+
+    assert_eq!(client.sweep_expired(&player), amount);
+    assert!(client.lots(&player).is_empty());
+    assert_eq!(client.get_votes(&player), 0);
+}
+
+#[test]
+fn test_expired_lots_are_burned_on_activity() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let player = Address::generate(&e);
+    let idle = Address::generate(&e);
+    let day: u64 = 24 * 60 * 60;
+    let amount: i128 = 10 * 10i128.pow(7);
+
+    client.set_lot_lifetime(&(365 * day));
+    client.mint(&player, &amount);
+    client.mint(&idle, &amount);
+
+    e.ledger().set_timestamp(366 * day);
+    client.transfer(&owner, &player, &5);
+    let burn = vec![&e, symbol_short!("burn").into_val(&e), player.into_val(&e)];
+    assert!(e.events().all().iter().any(|(_, topics, _)| topics == burn));
+    assert_eq!(client.lots(&player).len(), 1);
+    assert_eq!(client.get_votes(&player), 5);
+
+    // Idle balances are left for the owner to sweep.
+    e.set_auths(&[]);
+    assert!(client.try_sweep_expired(&idle).is_err());
+    e.mock_all_auths();
+    assert_eq!(client.sweep_expired(&idle), amount);
+    assert_eq!(client.get_votes(&idle), 0);
+}
+
+#[test]
+fn test_lot_exempt_account_holds_non_expiring_lots() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let player = Address::generate(&e);
+    let bridge = Address::generate(&e);
+    let day: u64 = 24 * 60 * 60;
+    let amount: i128 = 10 * 10i128.pow(7);
+
+    client.set_lot_lifetime(&(30 * day));
+    client.mint(&player, &amount);
+    client.set_lot_exempt(&bridge, &true);
+    assert!(client.is_lot_exempt(&bridge));
+    client.transfer(&player, &bridge, &amount);
+
+    e.ledger().set_timestamp(60 * day);
+    assert_eq!(client.balance(&bridge), amount);
+    assert_eq!(client.sweep_expired(&bridge), 0);
+    assert_eq!(client.lots(&bridge).get(0).unwrap().expires_at, u64::MAX);
+}
+
+#[test]
+fn test_spends_consume_oldest_lots_first() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let player = Address::generate(&e);
+    let friend = Address::generate(&e);
+    let day: u64 = 24 * 60 * 60;
+
+    client.set_lot_lifetime(&(30 * day));
+    client.mint(&player, &100);
+    e.ledger().set_timestamp(10 * day);
+    client.mint(&player, &50);
+
+    // 120 spends all of the first lot and 20 of the second.
+    client.transfer(&player, &friend, &120);
+    let remaining = client.lots(&player);
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining.get_unchecked(0).amount, 30);
+    assert_eq!(remaining.get_unchecked(0).expires_at, 40 * day);
+
+    // Transferred tokens keep their original expiry.
+    let received = client.lots(&friend);
+    assert_eq!(received.len(), 2);
+    assert_eq!(received.get_unchecked(0).expires_at, 30 * day);
+
+    e.ledger().set_timestamp(30 * day);
+    assert_eq!(client.balance(&friend), 20);
+    assert_eq!(client.balance(&player), 30);
+}
+
+#[test]
+fn test_transfer_fee_goes_to_treasury() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let treasury = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+
+    assert_eq!(client.transfer_fee(), 0);
+    client.set_treasury(&treasury);
+    client.set_fee_exempt(&owner, &true);
+    client.set_transfer_fee(&100); // 1%
+
+    // Exempt sender pays no fee.
+    client.transfer(&owner, &alice, &10_000);
+    assert_eq!(client.balance(&alice), 10_000);
+    assert_eq!(client.balance(&treasury), 0);
+
+    client.transfer(&alice, &bob, &5_000);
+    // The fee shows up as its own transfer so indexers can track the treasury.
+    let fee_transfer = vec![
+        &e,
+        symbol_short!("transfer").into_val(&e),
+        alice.into_val(&e),
+        treasury.into_val(&e),
+    ];
+    assert!(e
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == fee_transfer));
+    assert_eq!(client.balance(&alice), 5_000);
+    assert_eq!(client.balance(&bob), 4_950);
+    assert_eq!(client.balance(&treasury), 50);
+
+    // transfer_from spends the full amount, fee included, from the allowance.
+    client.approve(&alice, &bob, &2_000, &1000);
+    client.transfer_from(&bob, &alice, &bob, &2_000);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    assert_eq!(client.balance(&bob), 4_950 + 1_980);
+    assert_eq!(client.balance(&treasury), 70);
+}
+
+#[test]
+fn test_transfer_fee_is_capped() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+
+    assert!(client.try_set_transfer_fee(&1_001).is_err());
+}
+
+#[test]
+fn test_transfer_batch() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let partner = Address::generate(&e);
+    let alice = Address::generate(&e);
+    // Only account addresses can carry a muxed ID.
+    let bob_muxed = MuxedAddress::new(MuxedAddress::generate(&e), 7);
+    let bob = bob_muxed.address();
+    client.transfer(&owner, &partner, &1_000);
+
+    client.transfer_batch(
+        &partner,
+        &vec![
+            &e,
+            (soroban_sdk::MuxedAddress::from(alice.clone()), 300),
+            (bob_muxed, 200),
+        ],
+    );
+    // One signature from the partner covers the whole batch.
+    assert_eq!(e.auths().len(), 1);
+    let to_bob = vec![
+        &e,
+        symbol_short!("transfer").into_val(&e),
+        partner.into_val(&e),
+        bob.into_val(&e),
+    ];
+    let (_, _, data) = e
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| *topics == to_bob)
+        .unwrap();
+    assert_eq!(
+        Map::<Symbol, Val>::try_from_val(&e, &data).unwrap(),
+        map![
+            &e,
+            (symbol_short!("amount"), 200i128.into_val(&e)),
+            (Symbol::new(&e, "to_muxed_id"), Some(7u64).into_val(&e)),
+        ]
+    );
+
+    assert_eq!(client.balance(&partner), 500);
+    assert_eq!(client.balance(&alice), 300);
+    assert_eq!(client.balance(&bob), 200);
+}
+
+#[test]
+fn test_transfer_batch_is_atomic() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let partner = Address::generate(&e);
+    let alice = Address::generate(&e);
+    let frozen = Address::generate(&e);
+    client.transfer(&owner, &partner, &1_000);
+    client.freeze(&frozen);
+
+    let result = client.try_transfer_batch(
+        &partner,
+        &vec![
+            &e,
+            (soroban_sdk::MuxedAddress::from(alice.clone()), 300),
+            (soroban_sdk::MuxedAddress::from(frozen.clone()), 200),
+        ],
+    );
+
+    assert!(result.is_err());
+    assert_eq!(client.balance(&partner), 1_000);
+    assert_eq!(client.balance(&alice), 0);
+}
+
+#[test]
+fn test_transfer_with_ref() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let venue = Address::generate(&e);
+    let reference = Bytes::from_slice(&e, b"spend-session:7f3c2a");
+
+    client.transfer_with_ref(&owner, &venue, &500, &reference);
+
+    let (contract, topics, data) = e.events().all().last().unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!(
+        topics,
+        vec![
+            &e,
+            Symbol::new(&e, "transfer_reference").into_val(&e),
+            owner.into_val(&e),
+            venue.into_val(&e),
+        ]
+    );
+    assert_eq!(
+        Map::<Symbol, Val>::try_from_val(&e, &data).unwrap(),
+        map![
+            &e,
+            (symbol_short!("amount"), 500i128.into_val(&e)),
+            (symbol_short!("fee"), 0i128.into_val(&e)),
+            (symbol_short!("reference"), reference.into_val(&e)),
+        ]
+    );
+    assert_eq!(client.balance(&venue), 500);
+}
+
+#[test]
+fn test_transfer_with_ref_reports_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let player = Address::generate(&e);
+    let venue = Address::generate(&e);
+    let treasury = Address::generate(&e);
+    let reference = Bytes::from_slice(&e, b"spend-session:7f3c2a");
+    client.set_treasury(&treasury);
+    client.set_fee_exempt(&owner, &true);
+    client.set_transfer_fee(&100); // 1%
+    client.transfer(&owner, &player, &1_000);
+
+    client.transfer_with_ref(&player, &venue, &500, &reference);
+
+    let (_, _, data) = e.events().all().last().unwrap();
+    assert_eq!(
+        Map::<Symbol, Val>::try_from_val(&e, &data).unwrap(),
+        map![
+            &e,
+            (symbol_short!("amount"), 495i128.into_val(&e)),
+            (symbol_short!("fee"), 5i128.into_val(&e)),
+            (symbol_short!("reference"), reference.into_val(&e)),
+        ]
+    );
+    assert_eq!(client.balance(&venue), 495);
+    assert_eq!(client.balance(&treasury), 5);
+}
+
+#[test]
+fn test_transfer_with_ref_rejects_long_reference() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let venue = Address::generate(&e);
+    let reference = Bytes::from_slice(&e, &[0u8; 65]);
+
+    assert!(client
+        .try_transfer_with_ref(&owner, &venue, &500, &reference,)
+        .is_err());
+}
+
+#[test]
+fn test_redeem_burns_and_stores_receipt() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let player = Address::generate(&e);
+    let perk_id = soroban_sdk::String::from_str(&e, "5b1f6c0e-9c1a-4d55-8c2e-1f0a7e2b9d11");
+    client.transfer(&owner, &player, &1_000);
+
+    e.ledger().set_sequence_number(42);
+    let receipt_id = client.redeem(&player, &perk_id, &400);
+
+    assert_eq!(receipt_id, 1);
+    assert_eq!(client.balance(&player), 600);
+    let receipt = client.receipt(&receipt_id);
+    assert_eq!(receipt.perk_id, perk_id);
+    assert_eq!(receipt.player, player);
+    assert_eq!(receipt.amount, 400);
+    assert_eq!(receipt.ledger, 42);
+
+    assert_eq!(client.redeem(&player, &perk_id, &100), 2);
+    assert!(client.try_receipt(&3).is_err());
+}
+
+#[test]
+fn test_minter_mint_requires_minter_role() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let minter = Address::generate(&e);
+    let player = Address::generate(&e);
+
+    assert!(client.try_minter_mint(&minter, &player, &100).is_err());
+
+    client.add_minter(&minter);
+    assert!(client.is_minter(&minter));
+    client.set_lot_lifetime(&(24 * 60 * 60));
+    client.minter_mint(&minter, &player, &100);
+    assert_eq!(client.balance(&player), 100);
+    // Backed mints ignore the lot lifetime.
+    assert_eq!(client.lots(&player).get(0).unwrap().expires_at, u64::MAX);
+
+    client.remove_minter(&minter);
+    assert!(client.try_minter_mint(&minter, &player, &100).is_err());
+}
+
+#[test]
+fn test_lifetime_earned_only_grows_on_mint() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, owner) = setup(&e);
+    let player = Address::generate(&e);
+    let friend = Address::generate(&e);
+
+    // The initial supply is not earned.
+    assert_eq!(client.lifetime_earned(&owner), 0);
+
+    client.mint(&player, &300);
+    client.mint_with_reason(
+        &player,
+        &200,
+        &symbol_short!("checkin"),
+        &BytesN::from_array(&e, &[1u8; 32]),
+    );
+    assert_eq!(client.lifetime_earned(&player), 500);
+
+    client.transfer(&player, &friend, &100);
+    client.burn(&player, &100);
+    client.transfer(&owner, &player, &1_000);
+
+    assert_eq!(client.balance(&player), 1_300);
+    assert_eq!(client.lifetime_earned(&player), 500);
+    assert_eq!(client.lifetime_earned(&friend), 0);
+}
+
+#[test]
+fn test_emission_schedule_limits_minting_per_epoch() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let minter = Address::generate(&e);
+    let player = Address::generate(&e);
+    let week: u64 = 7 * 24 * 60 * 60;
+    client.add_minter(&minter);
+
+    assert_eq!(client.emission_remaining(), None);
+    client.set_emission_schedule(&week, &1_000, &0, &false);
+
+    // Owner and minters share one allowance.
+    client.mint(&player, &600);
+    client.minter_mint(&minter, &player, &300);
+    assert_eq!(client.emission_remaining(), Some(100));
+    assert!(client.try_mint(&player, &101).is_err());
+    assert!(client.try_minter_mint(&minter, &player, &101).is_err());
+    assert!(client
+        .try_set_emission_schedule(&(60 * 60), &1_000, &0, &false)
+        .is_err());
+
+    // Unused allowance does not roll over by default.
+    e.ledger().set_timestamp(week);
+    assert_eq!(client.current_epoch(), 1);
+    assert_eq!(client.emission_remaining(), Some(1_000));
+
+    client.clear_emission_schedule();
+    client.mint(&player, &5_000);
+    assert_eq!(client.emission_remaining(), None);
+}
+
+#[test]
+fn test_replacing_emission_schedule_keeps_epoch_mints() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let player = Address::generate(&e);
+    let week: u64 = 7 * 24 * 60 * 60;
+
+    client.set_emission_schedule(&week, &1_000, &0, &false);
+    e.ledger().set_timestamp(week + 60);
+    client.mint(&player, &600);
+
+    // Re-setting the schedule mid-epoch does not refill the allowance.
+    client.set_emission_schedule(&week, &1_000, &0, &false);
+    assert_eq!(client.current_epoch(), 0);
+    assert_eq!(client.emission_remaining(), Some(400));
+    client.set_emission_schedule(&week, &500, &0, &false);
+    assert_eq!(client.emission_remaining(), Some(0));
+    assert!(client.try_mint(&player, &1).is_err());
+}
+
+#[test]
+fn test_emission_schedule_decay_and_rollover() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let player = Address::generate(&e);
+    let week: u64 = 7 * 24 * 60 * 60;
+
+    // 1_000 in epoch 0, shrinking 10% per epoch, unused allowance rolls over.
+    client.set_emission_schedule(&week, &1_000, &1_000, &true);
+    client.mint(&player, &400);
+
+    // Epoch 2: 600 left from epoch 0, 900 from epoch 1, plus 810 for epoch 2.
+    e.ledger().set_timestamp(2 * week);
+    assert_eq!(client.emission_remaining(), Some(600 + 900 + 810));
+    client.mint(&player, &2_310);
+    assert_eq!(client.emission_remaining(), Some(0));
+}
+
+#[test]
+fn test_emission_schedule_catches_up_after_long_idle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (client, _owner) = setup(&e);
+    let player = Address::generate(&e);
+    let day: u64 = 24 * 60 * 60;
+
+    client.set_emission_schedule(&day, &1_000, &0, &true);
+    e.ledger().set_timestamp(3_650 * day);
+    assert_eq!(client.emission_remaining(), Some(3_651 * 1_000));
+
+    client.set_emission_schedule(&day, &1_000_000, &1, &false);
+    e.ledger().set_timestamp(2 * 3_650 * day);
+    client.mint(&player, &100);
+    assert!(client.emission_remaining().unwrap() < 1_000_000);
+}
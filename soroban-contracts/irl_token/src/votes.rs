@@ -0,0 +1,165 @@
+//! Voting power checkpoints for IRL holders.
+//!
+//! Every account delegates its voting power to exactly one address, itself by
+//! default. Each delegate keeps a list of `(ledger, votes)` checkpoints written
+//! whenever its voting power changes, so votes at any past ledger can be looked
+//! up and tokens moved mid-vote do not count twice.
+
+use soroban_sdk::{contractevent, contracttype, Address, Env};
+
+use crate::DataKey;
+
+/// Voting power of a delegate from `ledger` onwards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub votes: i128,
+}
+
+/// Emitted when an account changes its delegate.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateChanged {
+    #[topic]
+    pub delegator: Address,
+    pub from_delegate: Address,
+    pub to_delegate: Address,
+}
+
+/// Emitted when a delegate's voting power changes.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateVotesChanged {
+    #[topic]
+    pub delegate: Address,
+    pub previous_votes: i128,
+    pub new_votes: i128,
+}
+
+/// Returns the address `account` delegates to (itself unless changed).
+pub fn delegates(e: &Env, account: &Address) -> Address {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Delegate(account.clone()))
+        .unwrap_or_else(|| account.clone())
+}
+
+/// Points `account`'s voting power (its full `balance`) at `delegatee`.
+pub fn delegate(e: &Env, account: &Address, delegatee: &Address, balance: i128) {
+    let current = delegates(e, account);
+    if current == *delegatee {
+        return;
+    }
+    e.storage()
+        .persistent()
+        .set(&DataKey::Delegate(account.clone()), delegatee);
+    DelegateChanged {
+        delegator: account.clone(),
+        from_delegate: current.clone(),
+        to_delegate: delegatee.clone(),
+    }
+    .publish(e);
+    move_votes(e, Some(&current), Some(delegatee), balance);
+}
+
+/// Moves voting power along with a balance change between `from` and `to`
+/// (`None` for mints and burns).
+pub fn transfer_voting_units(e: &Env, from: Option<&Address>, to: Option<&Address>, amount: i128) {
+    let from_delegate = from.map(|a| delegates(e, a));
+    let to_delegate = to.map(|a| delegates(e, a));
+    move_votes(e, from_delegate.as_ref(), to_delegate.as_ref(), amount);
+}
+
+/// Returns the current voting power of `account`.
+pub fn get_votes(e: &Env, account: &Address) -> i128 {
+    let count = num_checkpoints(e, account);
+    if count == 0 {
+        return 0;
+    }
+    checkpoint(e, account, count - 1).votes
+}
+
+/// Returns the voting power of `account` at the end of `ledger`, which must be
+/// in the past.
+pub fn get_past_votes(e: &Env, account: &Address, ledger: u32) -> i128 {
+    if ledger >= e.ledger().sequence() {
+        panic!("Ledger {} is not yet finalized", ledger);
+    }
+    // Find the last checkpoint written at or before `ledger`.
+    let mut low = 0;
+    let mut high = num_checkpoints(e, account);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if checkpoint(e, account, mid).ledger > ledger {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    if high == 0 {
+        0
+    } else {
+        checkpoint(e, account, high - 1).votes
+    }
+}
+
+fn move_votes(e: &Env, from: Option<&Address>, to: Option<&Address>, amount: i128) {
+    if amount == 0 || from == to {
+        return;
+    }
+    if let Some(from) = from {
+        let previous = get_votes(e, from);
+        write_checkpoint(e, from, previous, previous - amount);
+    }
+    if let Some(to) = to {
+        let previous = get_votes(e, to);
+        write_checkpoint(e, to, previous, previous + amount);
+    }
+}
+
+fn write_checkpoint(e: &Env, delegate: &Address, previous_votes: i128, new_votes: i128) {
+    let ledger = e.ledger().sequence();
+    let count = num_checkpoints(e, delegate);
+    // Several changes in the same ledger collapse into one checkpoint.
+    if count > 0 && checkpoint(e, delegate, count - 1).ledger == ledger {
+        e.storage().persistent().set(
+            &DataKey::Checkpoint(delegate.clone(), count - 1),
+            &Checkpoint {
+                ledger,
+                votes: new_votes,
+            },
+        );
+    } else {
+        e.storage().persistent().set(
+            &DataKey::Checkpoint(delegate.clone(), count),
+            &Checkpoint {
+                ledger,
+                votes: new_votes,
+            },
+        );
+        e.storage()
+            .persistent()
+            .set(&DataKey::NumCheckpoints(delegate.clone()), &(count + 1));
+    }
+    DelegateVotesChanged {
+        delegate: delegate.clone(),
+        previous_votes,
+        new_votes,
+    }
+    .publish(e);
+}
+
+fn num_checkpoints(e: &Env, account: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::NumCheckpoints(account.clone()))
+        .unwrap_or(0)
+}
+
+fn checkpoint(e: &Env, account: &Address, index: u32) -> Checkpoint {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Checkpoint(account.clone(), index))
+        .expect("Checkpoint not found")
+}
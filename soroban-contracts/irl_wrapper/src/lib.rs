@@ -0,0 +1,169 @@
+#![no_std]
+//! Classic-asset wrapper for IRL.
+//!
+//! Locks the classic IRL Stellar asset (through its Stellar Asset Contract) and
+//! mints `IRLToken` 1:1 via the token's minter path; burning `IRLToken`
+//! releases the classic asset again. Both use 7 decimals, so amounts map 1:1.
+
+use soroban_sdk::{
+    contract, contractclient, contractevent, contractimpl, contracttype, token, Address, Env,
+};
+
+/// The subset of `IRLToken` the wrapper calls.
+#[allow(dead_code)]
+#[contractclient(name = "IRLTokenClient")]
+pub trait IRLTokenInterface {
+    fn minter_mint(e: Env, minter: Address, to: Address, amount: i128);
+    fn burn(e: Env, from: Address, amount: i128);
+    fn is_transferable(e: Env) -> bool;
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Stellar Asset Contract address of the classic IRL asset.
+    Asset,
+    /// `IRLToken` contract address.
+    Token,
+    /// Classic IRL an account has locked and not yet unwrapped.
+    Wrapped(Address),
+}
+
+/// Emitted when classic IRL is locked and `IRLToken` minted.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Wrapped {
+    #[topic]
+    pub account: Address,
+    pub amount: i128,
+}
+
+/// Emitted when `IRLToken` is burned and classic IRL released.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unwrapped {
+    #[topic]
+    pub account: Address,
+    pub amount: i128,
+}
+
+#[contract]
+pub struct IRLWrapper;
+
+#[contractimpl]
+impl IRLWrapper {
+    /// Constructor to initialize the wrapper
+    ///
+    /// # Arguments
+    /// * `asset` - Stellar Asset Contract address of the classic IRL asset
+    /// * `irl_token` - `IRLToken` contract address
+    ///
+    /// # Note
+    /// The wrapper must be added as a minter on `IRLToken` (`add_minter`) before
+    /// `wrap` can be used. Panics if `irl_token` is non-transferable, since
+    /// wrapped IRL must be able to move like the classic asset it stands for.
+    ///
+    /// To get the asset contract address for your network:
+    /// ```bash
+    /// soroban contract id asset --asset IRL:ISSUER_ADDRESS --network <network>
+    /// ```
+    pub fn __constructor(e: &Env, asset: Address, irl_token: Address) {
+        if !IRLTokenClient::new(e, &irl_token).is_transferable() {
+            panic!("IRL token is non-transferable");
+        }
+        e.storage().instance().set(&DataKey::Asset, &asset);
+        e.storage().instance().set(&DataKey::Token, &irl_token);
+    }
+
+    /// Returns the classic asset's Stellar Asset Contract address.
+    pub fn asset(e: &Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .expect("Asset address not initialized")
+    }
+
+    /// Returns the `IRLToken` contract address.
+    pub fn token(e: &Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .expect("Token address not initialized")
+    }
+
+    /// Returns the amount of classic IRL currently locked in the wrapper.
+    pub fn locked(e: &Env) -> i128 {
+        token::Client::new(e, &Self::asset(e)).balance(&e.current_contract_address())
+    }
+
+    /// Returns the classic IRL `account` has wrapped and not yet unwrapped, the
+    /// most it can unwrap.
+    pub fn wrapped(e: &Env, account: Address) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Wrapped(account))
+            .unwrap_or(0)
+    }
+
+    /// Lock `amount` of classic IRL from `from` and mint the same amount of
+    /// `IRLToken` to `from`.
+    pub fn wrap(e: &Env, from: Address, amount: i128) {
+        if amount <= 0 {
+            panic!("Amount must be positive, got: {}", amount);
+        }
+        from.require_auth();
+
+        let contract_address = e.current_contract_address();
+        token::Client::new(e, &Self::asset(e)).transfer(&from, &contract_address, &amount);
+        IRLTokenClient::new(e, &Self::token(e)).minter_mint(&contract_address, &from, &amount);
+        let wrapped = Self::wrapped(e, from.clone()) + amount;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Wrapped(from.clone()), &wrapped);
+
+        Wrapped {
+            account: from,
+            amount,
+        }
+        .publish(e);
+    }
+
+    /// Burn `amount` of `IRLToken` from `from` and release the same amount of
+    /// classic IRL to `from`.
+    ///
+    /// # Note
+    /// Fails if `from` has wrapped less than `amount` (see `wrapped`), so IRL
+    /// minted by the owner can never release classic IRL locked by others.
+    pub fn unwrap(e: &Env, from: Address, amount: i128) {
+        if amount <= 0 {
+            panic!("Amount must be positive, got: {}", amount);
+        }
+        from.require_auth();
+
+        let wrapped = Self::wrapped(e, from.clone());
+        if amount > wrapped {
+            panic!(
+                "Insufficient wrapped balance: requested {} but {} wrapped",
+                amount, wrapped
+            );
+        }
+        e.storage()
+            .persistent()
+            .set(&DataKey::Wrapped(from.clone()), &(wrapped - amount));
+        IRLTokenClient::new(e, &Self::token(e)).burn(&from, &amount);
+        token::Client::new(e, &Self::asset(e)).transfer(
+            &e.current_contract_address(),
+            &from,
+            &amount,
+        );
+
+        Unwrapped {
+            account: from,
+            amount,
+        }
+        .publish(e);
+    }
+}
+
+#[cfg(test)]
+mod test;
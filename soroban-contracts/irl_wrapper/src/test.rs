@@ -0,0 +1,129 @@
+//! Tests for the classic-asset wrapper, against a Stellar Asset Contract from testutils.
+
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+use irl_token::{IRLToken, IRLTokenClient};
+
+use crate::{IRLWrapper, IRLWrapperClient};
+
+struct Setup<'a> {
+    owner: Address,
+    wrapper: IRLWrapperClient<'a>,
+    irl: IRLTokenClient<'a>,
+    asset: token::TokenClient<'a>,
+    asset_admin: token::StellarAssetClient<'a>,
+}
+
+fn setup(e: &Env) -> Setup<'_> {
+    let owner = Address::generate(e);
+    let issuer = Address::generate(e);
+    let sac = e.register_stellar_asset_contract_v2(issuer);
+    let irl_id = e.register(IRLToken, (&owner, false));
+    let wrapper_id = e.register(IRLWrapper, (&sac.address(), &irl_id));
+
+    let irl = IRLTokenClient::new(e, &irl_id);
+    irl.add_minter(&wrapper_id);
+
+    Setup {
+        owner,
+        wrapper: IRLWrapperClient::new(e, &wrapper_id),
+        irl,
+        asset: token::TokenClient::new(e, &sac.address()),
+        asset_admin: token::StellarAssetClient::new(e, &sac.address()),
+    }
+}
+
+#[test]
+fn test_wrap_and_unwrap() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let s = setup(&e);
+    let player = Address::generate(&e);
+    s.asset_admin.mint(&player, &1_000);
+
+    s.wrapper.wrap(&player, &600);
+    assert_eq!(s.asset.balance(&player), 400);
+    assert_eq!(s.wrapper.locked(), 600);
+    assert_eq!(s.irl.balance(&player), 600);
+
+    s.wrapper.unwrap(&player, &250);
+    assert_eq!(s.asset.balance(&player), 650);
+    assert_eq!(s.wrapper.locked(), 350);
+    assert_eq!(s.irl.balance(&player), 350);
+}
+
+#[test]
+fn test_wrap_round_trips_do_not_count_as_earned() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let s = setup(&e);
+    let player = Address::generate(&e);
+    s.asset_admin.mint(&player, &1_000);
+
+    for _ in 0..3 {
+        s.wrapper.wrap(&player, &1_000);
+        s.wrapper.unwrap(&player, &1_000);
+    }
+    assert_eq!(s.irl.lifetime_earned(&player), 0);
+}
+
+#[test]
+fn test_wrap_requires_minter_role() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let s = setup(&e);
+    let player = Address::generate(&e);
+    s.asset_admin.mint(&player, &1_000);
+    s.irl.remove_minter(&s.wrapper.address);
+
+    assert!(s.wrapper.try_wrap(&player, &600).is_err());
+    assert_eq!(s.asset.balance(&player), 1_000);
+}
+
+#[test]
+fn test_unwrap_limited_to_locked_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let s = setup(&e);
+    let player = Address::generate(&e);
+    s.asset_admin.mint(&player, &1_000);
+    s.wrapper.wrap(&player, &100);
+
+    assert!(s.wrapper.try_unwrap(&player, &101).is_err());
+    assert_eq!(s.irl.balance(&player), 100);
+}
+
+#[test]
+fn test_unwrap_limited_to_own_wrapped_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let s = setup(&e);
+    let player = Address::generate(&e);
+    let friend = Address::generate(&e);
+    s.asset_admin.mint(&player, &1_000);
+    s.wrapper.wrap(&player, &1_000);
+
+    // The owner's initial supply is not backed by locked classic IRL.
+    assert!(s.irl.balance(&s.owner) > 0);
+    assert!(s.wrapper.try_unwrap(&s.owner, &1).is_err());
+
+    // Wrapped IRL moved to another holder is not theirs to unwrap either.
+    s.irl.transfer(&player, &friend, &400);
+    assert!(s.wrapper.try_unwrap(&friend, &400).is_err());
+    assert_eq!(s.wrapper.wrapped(&player), 1_000);
+    assert_eq!(s.wrapper.locked(), 1_000);
+
+    s.wrapper.unwrap(&player, &600);
+    assert_eq!(s.wrapper.wrapped(&player), 400);
+    assert_eq!(s.asset.balance(&player), 600);
+}
+
+#[test]
+#[should_panic(expected = "IRL token is non-transferable")]
+fn test_rejects_non_transferable_token() {
+    let e = Env::default();
+    let owner = Address::generate(&e);
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    let irl_id = e.register(IRLToken, (&owner, true));
+    e.register(IRLWrapper, (&sac.address(), &irl_id));
+}
@@ -23,17 +23,22 @@ echo ""
 # Function to print usage
 usage() {
     echo -e "${YELLOW}Usage:${NC}"
-    echo "  ./deploy.sh <NETWORK> <SECRET_KEY> [OWNER_ADDRESS] [MAX_SUPPLY]"
+    echo "  ./deploy.sh <NETWORK> <SECRET_KEY> [OWNER_ADDRESS] [MAX_SUPPLY] [BASE_URI] [NAME] [SYMBOL] [SOULBOUND]"
     echo ""
     echo -e "${YELLOW}Arguments:${NC}"
     echo "  NETWORK        - Network to deploy to: testnet, mainnet, or futurenet"
     echo "  SECRET_KEY     - Your Stellar account secret key (starts with 'S')"
     echo "  OWNER_ADDRESS  - Owner address (optional, defaults to account from SECRET_KEY)"
     echo "  MAX_SUPPLY     - Maximum NFT supply (optional, defaults to 10000, use 0 for unlimited)"
+    echo "  BASE_URI       - Metadata JSON URL (optional, defaults to the IRL test collection metadata)"
+    echo "  NAME           - Collection name (optional, defaults to \"IRL Test Collection\")"
+    echo "  SYMBOL         - Collection symbol (optional, defaults to IRL001)"
+    echo "  SOULBOUND      - true for non-transferable badges (optional, defaults to false)"
     echo ""
     echo -e "${YELLOW}Examples:${NC}"
     echo "  ./deploy.sh testnet SCKH34BJYI7LZNHFWK4URGVIQ3Q6NSSHW3JBVQVJ3HLPOBJDMBW6FAX4"
     echo "  ./deploy.sh mainnet SCKH34BJYI7LZNHFWK4URGVIQ3Q6NSSHW3JBVQVJ3HLPOBJDMBW6FAX4 GBPOXKFVX4BRAQHMNE66EWKPVQRE6ZR3P4WQGKIA4WUFVX7K3NN6SWA6 50000"
+    echo "  ./deploy.sh mainnet SCKH34BJYI7LZNHFWK4URGVIQ3Q6NSSHW3JBVQVJ3HLPOBJDMBW6FAX4 GBPOXKFVX4BRAQHMNE66EWKPVQRE6ZR3P4WQGKIA4WUFVX7K3NN6SWA6 500 https://gateway.pinata.cloud/ipfs/YOUR_METADATA_JSON_CID \"Partner Badges\" PARTNER"
     echo ""
     exit 1
 }
@@ -48,6 +53,10 @@ NETWORK="$1"
 SECRET_KEY="$2"
 OWNER_ADDRESS="${3:-}"
 MAX_SUPPLY="${4:-10000}"
+BASE_URI="${5:-https://gateway.pinata.cloud/ipfs/bafkreifdsya4dc3cgv7dwfq4az76apqkomgqlivxwbmjertxzvn2jsjc5q}"
+COLLECTION_NAME="${6:-IRL Test Collection}"
+COLLECTION_SYMBOL="${7:-IRL001}"
+SOULBOUND="${8:-false}"
 
 # Validate network
 if [[ ! "$NETWORK" =~ ^(testnet|mainnet|futurenet)$ ]]; then
@@ -79,6 +88,12 @@ if [[ ! "$OWNER_ADDRESS" =~ ^G[A-Z0-9]{55}$ ]]; then
     exit 1
 fi
 
+# Validate soulbound flag
+if [[ ! "$SOULBOUND" =~ ^(true|false)$ ]]; then
+    echo -e "${RED}Error: SOULBOUND must be true or false${NC}"
+    exit 1
+fi
+
 # Validate max supply
 if ! [[ "$MAX_SUPPLY" =~ ^[0-9]+$ ]]; then
     echo -e "${RED}Error: MAX_SUPPLY must be a non-negative integer${NC}"
@@ -89,6 +104,10 @@ echo -e "${GREEN}Deployment Configuration:${NC}"
 echo -e "  Network:        ${BLUE}${NETWORK}${NC}"
 echo -e "  Owner:          ${GREEN}${OWNER_ADDRESS}${NC}"
 echo -e "  Max Supply:     ${GREEN}${MAX_SUPPLY}${NC} (0 = unlimited)"
+echo -e "  Base URI:       ${GREEN}${BASE_URI}${NC}"
+echo -e "  Name:           ${GREEN}${COLLECTION_NAME}${NC}"
+echo -e "  Symbol:         ${GREEN}${COLLECTION_SYMBOL}${NC}"
+echo -e "  Soulbound:      ${GREEN}${SOULBOUND}${NC}"
 echo ""
 
 # Get XLM native asset contract address for the network
@@ -169,7 +188,11 @@ DEPLOY_CMD="soroban contract deploy \
     -- \
     --owner \"${OWNER_ADDRESS}\" \
     --native_asset_address \"${XLM_ADDRESS}\" \
-    --max_supply ${MAX_SUPPLY}"
+    --max_supply ${MAX_SUPPLY} \
+    --base_uri \"${BASE_URI}\" \
+    --name \"${COLLECTION_NAME}\" \
+    --symbol \"${COLLECTION_SYMBOL}\" \
+    --soulbound ${SOULBOUND}"
 
 echo -e "${BLUE}Command:${NC}"
 echo "$DEPLOY_CMD" | sed 's/--source "[^"]*"/--source "S***"/'
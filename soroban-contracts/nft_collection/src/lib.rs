@@ -1,13 +1,172 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, token, Address, Env, String};
+// The constructor (and the client generated for it) takes eight arguments.
+#![allow(clippy::too_many_arguments)]
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Map, String, Vec,
+};
 use stellar_access::ownable::{self as ownable, Ownable};
 use stellar_contract_utils::pausable::{self as pausable, Pausable};
 use stellar_macros::{only_owner, when_not_paused};
 use stellar_tokens::non_fungible::{
     Base, burnable::NonFungibleBurnable, enumerable::{NonFungibleEnumerable, Enumerable},
-    ContractOverrides, NonFungibleToken,
+    royalties::NonFungibleRoyalties, ContractOverrides, NonFungibleToken,
 };  
 
+/// Keys for per-address and per-token entries in persistent storage.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Set for addresses allowed to mint without payment (e.g. the check-in backend).
+    Minter(Address),
+    /// Metadata URI of a single token, overriding the base URI.
+    TokenUri(u32),
+    /// Set once a token's URI has been permanently frozen.
+    TokenUriFrozen(u32),
+    /// Number of NFTs minted to an address.
+    MintedBy(Address),
+    /// Number of NFTs minted to an address in the phase starting at the given timestamp.
+    PhaseMinted(u64, Address),
+    /// Set once a check-in voucher nonce has been used.
+    VoucherUsed(u64),
+    /// Badge minted to a player for a location.
+    LocationBadge(Address, u64),
+    /// Location ID a badge was minted for.
+    BadgeLocation(u32),
+}
+
+/// Message the check-in server signs (ed25519, over the SHA-256 of its XDR) to let a
+/// player mint a badge for a location.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckInVoucher {
+    /// Network passphrase hash, so a voucher cannot be replayed on another network.
+    pub network_id: BytesN<32>,
+    /// This collection contract.
+    pub contract: Address,
+    pub player: Address,
+    pub location_id: u64,
+    /// Ledger timestamp after which the voucher can no longer be used.
+    pub expires_at: u64,
+    pub nonce: u64,
+}
+
+/// A time window with its own price, per-wallet cap and optional allowlist.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintPhase {
+    /// Ledger timestamp at which the phase opens.
+    pub start: u64,
+    /// Ledger timestamp at which the phase closes (exclusive).
+    pub end: u64,
+    /// The only payment token accepted during the phase.
+    pub payment_token: Address,
+    /// Price per mint in `payment_token`'s smallest unit (0 = free mint).
+    pub price: i128,
+    /// Maximum mints per address during the phase (0 = unlimited).
+    pub max_per_wallet: u32,
+    /// Merkle root of allowed addresses; `None` makes the phase public.
+    pub merkle_root: Option<BytesN<32>>,
+}
+
+/// Default XLM mint price set at deployment: 0.01 XLM in stroops.
+const DEFAULT_MINT_PRICE: i128 = 100_000;
+
+/// Emitted when the owner changes the mint price for a payment token.
+/// `None` means the token was not (or is no longer) accepted.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintPriceUpdated {
+    #[topic]
+    pub token: Address,
+    pub old_price: Option<i128>,
+    pub new_price: Option<i128>,
+}
+
+/// Emitted when the owner adds or removes a minter.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterUpdated {
+    #[topic]
+    pub minter: Address,
+    pub enabled: bool,
+}
+
+/// Emitted when a token's own metadata URI is set or changed.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenUriUpdated {
+    #[topic]
+    pub token_id: u32,
+    pub uri: String,
+}
+
+/// Emitted when a token's metadata URI is permanently frozen.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenUriFrozen {
+    #[topic]
+    pub token_id: u32,
+    pub uri: String,
+}
+
+/// Emitted when the owner changes the per-wallet mint limit.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxPerWalletUpdated {
+    pub max_per_wallet: u32,
+}
+
+/// Emitted when the owner replaces the mint phases.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintPhasesUpdated {
+    pub phases: Vec<MintPhase>,
+}
+
+/// Emitted when the owner sets the check-in voucher signing key.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoucherSignerUpdated {
+    pub public_key: BytesN<32>,
+}
+
+/// Emitted when a player mints a check-in badge with a voucher.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeClaimed {
+    #[topic]
+    pub player: Address,
+    #[topic]
+    pub location_id: u64,
+    pub token_id: u32,
+}
+
+/// Emitted for each NFT the owner or a minter gifts (`mint_with_uri`, `mint_to`, `airdrop`).
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Gifted {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub to: Address,
+    pub token_id: u32,
+}
+
+/// Emitted when the owner changes the base URI.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BaseUriUpdated {
+    pub base_uri: String,
+}
+
+/// Emitted when the owner permanently freezes the base URI.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BaseUriFrozen {
+    pub base_uri: String,
+}
+
 #[contract]
 pub struct NonFungibleTokenContract;
 
@@ -19,31 +178,42 @@ impl NonFungibleTokenContract {
     /// * `owner` - The address that will own the contract (for administrative purposes)
     /// * `native_asset_address` - The XLM native asset contract address for your network
     /// * `max_supply` - Maximum number of NFTs that can be minted (0 = unlimited)
+    /// * `base_uri` - Base URI for token metadata (use an HTTPS gateway for IPFS)
+    /// * `name` - The collection name
+    /// * `symbol` - The collection symbol
+    /// * `soulbound` - If true, tokens can never be transferred or approved, only burned by
+    ///   their holder (e.g. proof-of-attendance and tier badges)
     /// 
     /// # Note
     /// Sets the contract metadata (name, symbol, URI) and assigns the owner.
+    /// The owner can change the base URI later with `set_base_uri` until it is frozen.
     /// The owner can be used for future administrative functions, but any user can mint NFTs.
+    /// XLM is accepted as payment at 0.01 XLM per mint; the owner can add other payment
+    /// tokens (e.g. USDC or IRLToken) with `set_mint_price`.
     /// 
     /// To get the XLM contract address for your network:
     /// ```bash
     /// soroban contract id asset --asset native --network <network>
     /// ```
-    pub fn __constructor(e: &Env, owner: Address, native_asset_address: Address, max_supply: u32) {
+    pub fn __constructor(
+        e: &Env,
+        owner: Address,
+        native_asset_address: Address,
+        max_supply: u32,
+        base_uri: String,
+        name: String,
+        symbol: String,
+        soulbound: bool,
+    ) {
         // Set token metadata (use HTTPS gateway for IPFS - many wallets/explorers expect https://)
-        Base::set_metadata(
-            e,
-            String::from_str(e, "https://gateway.pinata.cloud/ipfs/bafkreifdsya4dc3cgv7dwfq4az76apqkomgqlivxwbmjertxzvn2jsjc5q"),
-            String::from_str(e, "IRL Test Collection"),
-            String::from_str(e, "IRL001"),
-        );
+        Base::set_metadata(e, base_uri, name, symbol);
 
         // Set the contract owner
         ownable::set_owner(e, &owner);
-        
-        // Store the native asset address in contract storage
-        // Note: Instance storage entries have TTL and may expire unless extended
-        let native_asset_key = String::from_str(e, "native_asset");
-        e.storage().instance().set(&native_asset_key, &native_asset_address);
+
+        // Soulbound mode is fixed at deployment
+        let soulbound_key = String::from_str(e, "soulbound");
+        e.storage().instance().set(&soulbound_key, &soulbound);
         
         // Store the max supply in contract storage
         // Note: Instance storage entries have TTL and may expire unless extended
@@ -54,17 +224,641 @@ impl NonFungibleTokenContract {
         // Note: Instance storage entries have TTL and may expire unless extended
         let supply_key = String::from_str(e, "supply");
         e.storage().instance().set(&supply_key, &0u32);
+
+        // Accept XLM at the default mint price; the owner can change prices and add
+        // other payment tokens with `set_mint_price`
+        let mut mint_prices: Map<Address, i128> = Map::new(e);
+        mint_prices.set(native_asset_address, DEFAULT_MINT_PRICE);
+        let mint_prices_key = String::from_str(e, "mint_prices");
+        e.storage().instance().set(&mint_prices_key, &mint_prices);
     }
 
-    /// Get the XLM native asset contract address from storage
-    /// 
-    /// Returns the native asset address that was set during contract initialization.
-    fn get_native_asset_address(env: &Env) -> Address {
-        let storage_key = String::from_str(env, "native_asset");
-        env.storage()
+    /// Get all accepted payment tokens and their mint prices
+    ///
+    /// Returns a map of payment token contract address to price per mint, in that
+    /// token's smallest unit (0 = free mint).
+    pub fn payment_tokens(e: &Env) -> Map<Address, i128> {
+        let storage_key = String::from_str(e, "mint_prices");
+        e.storage()
             .instance()
             .get(&storage_key)
-            .expect("Native asset address not initialized")
+            .unwrap_or_else(|| Map::new(e))
+    }
+
+    /// Get the mint price for a payment token
+    ///
+    /// # Arguments
+    /// * `token` - The payment token contract address
+    ///
+    /// Returns the price charged per mint in `token`'s smallest unit (0 = free mint).
+    /// Panics if `token` is not an accepted payment token.
+    pub fn mint_price(e: &Env, token: Address) -> i128 {
+        Self::payment_tokens(e)
+            .get(token)
+            .unwrap_or_else(|| panic!("Payment token not accepted"))
+    }
+
+    /// Set the mint price for a payment token, adding it if it is not yet accepted
+    ///
+    /// # Arguments
+    /// * `token` - The payment token contract address (e.g. the XLM, USDC or IRLToken contract)
+    /// * `price` - The new price per mint in `token`'s smallest unit. Use 0 for free mints.
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. Emits a `mint_price_updated` event.
+    #[only_owner]
+    pub fn set_mint_price(e: &Env, token: Address, price: i128) {
+        if price < 0 {
+            panic!("Mint price cannot be negative, got: {}", price);
+        }
+        let mut mint_prices = Self::payment_tokens(e);
+        let old_price = mint_prices.get(token.clone());
+        mint_prices.set(token.clone(), price);
+        let storage_key = String::from_str(e, "mint_prices");
+        e.storage().instance().set(&storage_key, &mint_prices);
+        MintPriceUpdated {
+            token,
+            old_price,
+            new_price: Some(price),
+        }
+        .publish(e);
+    }
+
+    /// Stop accepting a payment token for mints
+    ///
+    /// # Arguments
+    /// * `token` - The payment token contract address to remove
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. Fees already collected in `token`
+    /// can still be withdrawn. Emits a `mint_price_updated` event.
+    #[only_owner]
+    pub fn remove_payment_token(e: &Env, token: Address) {
+        let mut mint_prices = Self::payment_tokens(e);
+        let old_price = mint_prices.get(token.clone());
+        if old_price.is_none() {
+            panic!("Payment token not accepted");
+        }
+        mint_prices.remove(token.clone());
+        let storage_key = String::from_str(e, "mint_prices");
+        e.storage().instance().set(&storage_key, &mint_prices);
+        MintPriceUpdated {
+            token,
+            old_price,
+            new_price: None,
+        }
+        .publish(e);
+    }
+
+    /// Check whether tokens are soulbound (non-transferable)
+    pub fn is_soulbound(e: &Env) -> bool {
+        let storage_key = String::from_str(e, "soulbound");
+        e.storage()
+            .instance()
+            .get(&storage_key)
+            .unwrap_or(false)
+    }
+
+    /// Panic if the collection is soulbound
+    fn require_transferable(e: &Env) {
+        if Self::is_soulbound(e) {
+            panic!("Tokens are soulbound and cannot be transferred");
+        }
+    }
+
+    /// Set the base URI for token metadata
+    ///
+    /// # Arguments
+    /// * `base_uri` - The new base URI (use an HTTPS gateway for IPFS)
+    ///
+    /// # Note
+    /// Only the contract owner can call this function, and only until `freeze_base_uri`
+    /// has been called. Name and symbol are unchanged. Emits a `base_uri_updated` event.
+    #[only_owner]
+    pub fn set_base_uri(e: &Env, base_uri: String) {
+        if Self::base_uri_frozen(e) {
+            panic!("Base URI is frozen");
+        }
+        Base::set_metadata(e, base_uri.clone(), Base::name(e), Base::symbol(e));
+        BaseUriUpdated { base_uri }.publish(e);
+    }
+
+    /// Permanently freeze the base URI
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. After this, `set_base_uri` always
+    /// fails, so holders can rely on the metadata location never changing. This cannot be
+    /// undone. Emits a `base_uri_frozen` event.
+    #[only_owner]
+    pub fn freeze_base_uri(e: &Env) {
+        if Self::base_uri_frozen(e) {
+            panic!("Base URI is frozen");
+        }
+        let storage_key = String::from_str(e, "base_uri_frozen");
+        e.storage().instance().set(&storage_key, &true);
+        BaseUriFrozen {
+            base_uri: Base::base_uri(e),
+        }
+        .publish(e);
+    }
+
+    /// Check whether the base URI has been permanently frozen
+    pub fn base_uri_frozen(e: &Env) -> bool {
+        let storage_key = String::from_str(e, "base_uri_frozen");
+        e.storage()
+            .instance()
+            .get(&storage_key)
+            .unwrap_or(false)
+    }
+
+    /// Get the maximum number of NFTs that can be minted to one address (0 = unlimited)
+    pub fn max_per_wallet(e: &Env) -> u32 {
+        let storage_key = String::from_str(e, "max_per_wallet");
+        e.storage()
+            .instance()
+            .get(&storage_key)
+            .unwrap_or(0)
+    }
+
+    /// Set the maximum number of NFTs that can be minted to one address
+    ///
+    /// # Arguments
+    /// * `max_per_wallet` - The new limit. Use 0 for unlimited.
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. Mints already made still count
+    /// towards the new limit. Emits a `max_per_wallet_updated` event.
+    #[only_owner]
+    pub fn set_max_per_wallet(e: &Env, max_per_wallet: u32) {
+        let storage_key = String::from_str(e, "max_per_wallet");
+        e.storage().instance().set(&storage_key, &max_per_wallet);
+        MaxPerWalletUpdated { max_per_wallet }.publish(e);
+    }
+
+    /// Get the number of NFTs minted to an address
+    ///
+    /// Counts `mint` and `mint_with_voucher` mints to the address (gifts are not counted),
+    /// including ones since transferred or burned.
+    pub fn minted_by(e: &Env, account: Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::MintedBy(account))
+            .unwrap_or(0)
+    }
+
+    /// Get the configured mint phases, in start order
+    pub fn mint_phases(e: &Env) -> Vec<MintPhase> {
+        let storage_key = String::from_str(e, "mint_phases");
+        e.storage()
+            .instance()
+            .get(&storage_key)
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// Get the mint phase open at the current ledger timestamp, if any
+    pub fn current_mint_phase(e: &Env) -> Option<MintPhase> {
+        let now = e.ledger().timestamp();
+        Self::mint_phases(e)
+            .iter()
+            .find(|phase| phase.start <= now && now < phase.end)
+    }
+
+    /// Replace the mint phases
+    ///
+    /// # Arguments
+    /// * `phases` - The new phases, sorted by `start` and not overlapping. Pass an empty
+    ///   list to go back to open minting at the `payment_tokens` prices.
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. While any phases are configured,
+    /// `mint` only succeeds during one of them. Per-phase mint counts are keyed by the
+    /// phase's `start`, so editing a phase without moving its start keeps its counts.
+    /// Emits a `mint_phases_updated` event.
+    #[only_owner]
+    pub fn set_mint_phases(e: &Env, phases: Vec<MintPhase>) {
+        let mut previous_end = 0;
+        for phase in phases.iter() {
+            if phase.start >= phase.end {
+                panic!(
+                    "Phase must start before it ends: {} >= {}",
+                    phase.start, phase.end
+                );
+            }
+            if phase.start < previous_end {
+                panic!(
+                    "Phases must be sorted and not overlap: {} < {}",
+                    phase.start, previous_end
+                );
+            }
+            if phase.price < 0 {
+                panic!("Mint price cannot be negative, got: {}", phase.price);
+            }
+            previous_end = phase.end;
+        }
+        let storage_key = String::from_str(e, "mint_phases");
+        e.storage().instance().set(&storage_key, &phases);
+        MintPhasesUpdated { phases }.publish(e);
+    }
+
+    /// Set the ed25519 public key of the check-in server that signs vouchers
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. Vouchers signed by the previous key
+    /// stop working. Once a signer is set, public `mint` is disabled so badges can only be
+    /// minted with vouchers (or gifted by the owner and minters). Emits a
+    /// `voucher_signer_updated` event.
+    #[only_owner]
+    pub fn set_voucher_signer(e: &Env, public_key: BytesN<32>) {
+        let storage_key = String::from_str(e, "voucher_signer");
+        e.storage().instance().set(&storage_key, &public_key);
+        VoucherSignerUpdated { public_key }.publish(e);
+    }
+
+    /// Get the check-in voucher signing key, if set
+    pub fn voucher_signer(e: &Env) -> Option<BytesN<32>> {
+        let storage_key = String::from_str(e, "voucher_signer");
+        e.storage().instance().get(&storage_key)
+    }
+
+    /// Check whether a check-in voucher nonce has already been used
+    pub fn is_voucher_used(e: &Env, nonce: u64) -> bool {
+        e.storage().persistent().has(&DataKey::VoucherUsed(nonce))
+    }
+
+    /// Get the badge minted to a player for a location, if any
+    pub fn location_badge(e: &Env, player: Address, location_id: u64) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::LocationBadge(player, location_id))
+    }
+
+    /// Get the location ID a badge was minted for, if it was minted with a voucher
+    pub fn badge_location(e: &Env, token_id: u32) -> Option<u64> {
+        e.storage().persistent().get(&DataKey::BadgeLocation(token_id))
+    }
+
+    /// Mint a check-in badge with a voucher signed by the check-in server
+    ///
+    /// # Arguments
+    /// * `player` - The player who checked in; receives the badge
+    /// * `location_id` - The location or event the player checked in at
+    /// * `expires_at` - Ledger timestamp after which the voucher is no longer valid
+    /// * `nonce` - Voucher nonce; each can be used only once
+    /// * `signature` - ed25519 signature by `voucher_signer` over the SHA-256 of the
+    ///   XDR-encoded `CheckInVoucher`
+    ///
+    /// # Returns
+    /// Returns the token ID of the newly minted badge
+    ///
+    /// # Note
+    /// Anyone can submit a voucher; the badge always goes to `player`. No payment is taken.
+    /// Each player can hold at most one badge per location. `max_supply` and the per-wallet
+    /// limit still apply. Emits a `badge_claimed` event.
+    pub fn mint_with_voucher(
+        env: Env,
+        player: Address,
+        location_id: u64,
+        expires_at: u64,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> u32 {
+        if env.ledger().timestamp() >= expires_at {
+            panic!("Voucher expired at {}", expires_at);
+        }
+        if Self::is_voucher_used(&env, nonce) {
+            panic!("Voucher nonce {} already used", nonce);
+        }
+        if Self::location_badge(&env, player.clone(), location_id).is_some() {
+            panic!("Badge already minted for location {}", location_id);
+        }
+
+        let public_key = Self::voucher_signer(&env).expect("Voucher signer not set");
+        let voucher = CheckInVoucher {
+            network_id: env.ledger().network_id(),
+            contract: env.current_contract_address(),
+            player: player.clone(),
+            location_id,
+            expires_at,
+            nonce,
+        };
+        let digest: Bytes = env.crypto().sha256(&voucher.to_xdr(&env)).to_bytes().into();
+        env.crypto().ed25519_verify(&public_key, &digest, &signature);
+
+        if player == env.current_contract_address() {
+            panic!("Cannot mint to contract address");
+        }
+        Self::require_supply(&env, 1);
+        Self::record_wallet_mint(&env, &player);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::VoucherUsed(nonce), &true);
+        let token_id = Enumerable::sequential_mint(&env, &player);
+        Self::increment_supply(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LocationBadge(player.clone(), location_id), &token_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BadgeLocation(token_id), &location_id);
+
+        BadgeClaimed {
+            player,
+            location_id,
+            token_id,
+        }
+        .publish(&env);
+        token_id
+    }
+
+    /// Allow an address to mint with `mint_with_uri` and manage token URIs
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. Emits a `minter_updated` event.
+    #[only_owner]
+    pub fn add_minter(e: &Env, minter: Address) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Minter(minter.clone()), &true);
+        MinterUpdated {
+            minter,
+            enabled: true,
+        }
+        .publish(e);
+    }
+
+    /// Revoke a minter added with `add_minter`
+    ///
+    /// # Note
+    /// Only the contract owner can call this function. Emits a `minter_updated` event.
+    #[only_owner]
+    pub fn remove_minter(e: &Env, minter: Address) {
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Minter(minter.clone()));
+        MinterUpdated {
+            minter,
+            enabled: false,
+        }
+        .publish(e);
+    }
+
+    /// Check whether an address is a minter
+    pub fn is_minter(e: &Env, account: Address) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Minter(account))
+            .unwrap_or(false)
+    }
+
+    /// Mint a new NFT with its own metadata URI
+    ///
+    /// # Arguments
+    /// * `caller` - The owner or a minter; must authorize the call
+    /// * `to` - The address that will receive the newly minted NFT
+    /// * `uri` - Metadata URI for this token (e.g. the badge JSON for a check-in location)
+    ///
+    /// # Returns
+    /// Returns the token ID of the newly minted NFT
+    ///
+    /// # Note
+    /// No payment is taken and `to` does not need to sign. Same rules as `mint_to`.
+    /// Emits `gifted` and `token_uri_updated` events.
+    pub fn mint_with_uri(env: Env, caller: Address, to: Address, uri: String) -> u32 {
+        Self::require_owner_or_minter(&env, &caller);
+        Self::require_supply(&env, 1);
+
+        let token_id = Self::gift(&env, &caller, &to);
+        Self::write_token_uri(&env, token_id, uri);
+
+        token_id
+    }
+
+    /// Gift NFTs to an address without payment
+    ///
+    /// # Arguments
+    /// * `caller` - The owner or a minter; must authorize the call
+    /// * `to` - The address that will receive the NFTs
+    /// * `count` - How many NFTs to mint
+    ///
+    /// # Returns
+    /// Returns the token IDs of the newly minted NFTs
+    ///
+    /// # Note
+    /// No payment is taken and `to` does not need to sign. `max_supply` still applies, but
+    /// gifts neither count towards nor are limited by the per-wallet limit or mint phases.
+    /// Emits a `gifted` event per NFT.
+    pub fn mint_to(env: Env, caller: Address, to: Address, count: u32) -> Vec<u32> {
+        Self::require_owner_or_minter(&env, &caller);
+        if count == 0 {
+            panic!("Count must be positive");
+        }
+        Self::require_supply(&env, count);
+
+        let mut token_ids = Vec::new(&env);
+        for _ in 0..count {
+            token_ids.push_back(Self::gift(&env, &caller, &to));
+        }
+        token_ids
+    }
+
+    /// Gift one NFT to each address in a list without payment
+    ///
+    /// # Arguments
+    /// * `caller` - The owner or a minter; must authorize the call
+    /// * `recipients` - The addresses that will each receive one NFT (e.g. speakers, volunteers)
+    ///
+    /// # Returns
+    /// Returns the token IDs of the newly minted NFTs, in the order of `recipients`
+    ///
+    /// # Note
+    /// Same rules as `mint_to`. The whole airdrop fails if it would exceed `max_supply`.
+    pub fn airdrop(env: Env, caller: Address, recipients: Vec<Address>) -> Vec<u32> {
+        Self::require_owner_or_minter(&env, &caller);
+        if recipients.is_empty() {
+            panic!("No recipients");
+        }
+        Self::require_supply(&env, recipients.len());
+
+        let mut token_ids = Vec::new(&env);
+        for to in recipients.iter() {
+            token_ids.push_back(Self::gift(&env, &caller, &to));
+        }
+        token_ids
+    }
+
+    /// Mint one NFT from `caller` to `to` for the gift paths; the caller checks supply first
+    fn gift(env: &Env, caller: &Address, to: &Address) -> u32 {
+        if *to == env.current_contract_address() {
+            panic!("Cannot mint to contract address");
+        }
+        let token_id = Enumerable::sequential_mint(env, to);
+        Self::increment_supply(env);
+        Gifted {
+            caller: caller.clone(),
+            to: to.clone(),
+            token_id,
+        }
+        .publish(env);
+        token_id
+    }
+
+    /// Set or change the metadata URI of an existing token
+    ///
+    /// # Arguments
+    /// * `caller` - The owner or a minter; must authorize the call
+    /// * `token_id` - The token to update
+    /// * `uri` - The new metadata URI
+    ///
+    /// # Note
+    /// Fails once `freeze_token_uri` has been called for the token.
+    /// Emits a `token_uri_updated` event.
+    pub fn set_token_uri(env: Env, caller: Address, token_id: u32, uri: String) {
+        Self::require_owner_or_minter(&env, &caller);
+        // Panics if the token does not exist
+        Base::owner_of(&env, token_id);
+        if Self::token_uri_frozen(&env, token_id) {
+            panic!("Token URI is frozen: {}", token_id);
+        }
+        Self::write_token_uri(&env, token_id, uri);
+    }
+
+    /// Permanently freeze the metadata URI of a token
+    ///
+    /// # Arguments
+    /// * `caller` - The owner or a minter; must authorize the call
+    /// * `token_id` - The token whose URI to freeze
+    ///
+    /// # Note
+    /// Freezes whatever `token_uri` currently returns, so a token still using the base URI
+    /// is pinned to the current base URI even if it changes later. This cannot be undone.
+    /// Emits a `token_uri_frozen` event.
+    pub fn freeze_token_uri(env: Env, caller: Address, token_id: u32) {
+        Self::require_owner_or_minter(&env, &caller);
+        if Self::token_uri_frozen(&env, token_id) {
+            panic!("Token URI is frozen: {}", token_id);
+        }
+        let uri = Self::token_uri(&env, token_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenUri(token_id), &uri);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenUriFrozen(token_id), &true);
+        TokenUriFrozen { token_id, uri }.publish(&env);
+    }
+
+    /// Check whether a token's metadata URI has been permanently frozen
+    pub fn token_uri_frozen(e: &Env, token_id: u32) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::TokenUriFrozen(token_id))
+            .unwrap_or(false)
+    }
+
+    /// Store a token's own URI and emit `token_uri_updated`
+    fn write_token_uri(env: &Env, token_id: u32, uri: String) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenUri(token_id), &uri);
+        TokenUriUpdated { token_id, uri }.publish(env);
+    }
+
+    /// Require that `caller` authorized the call and is the owner or a minter
+    fn require_owner_or_minter(env: &Env, caller: &Address) {
+        caller.require_auth();
+        if ownable::get_owner(env).as_ref() != Some(caller)
+            && !Self::is_minter(env, caller.clone())
+        {
+            panic!("Caller is not the owner or a minter");
+        }
+    }
+
+    /// Require that `operator` authorized the call and is the owner
+    fn require_owner_operator(env: &Env, operator: &Address) {
+        operator.require_auth();
+        if ownable::get_owner(env).as_ref() != Some(operator) {
+            panic!("Operator is not the owner");
+        }
+    }
+
+    /// Panic if minting `count` more NFTs would exceed the maximum supply
+    fn require_supply(env: &Env, count: u32) {
+        let max_supply = Self::get_max_supply(env);
+        if max_supply > 0 {
+            let current_supply = Self::get_current_supply(env);
+            if current_supply >= max_supply {
+                panic!("Maximum supply reached: {} / {}", current_supply, max_supply);
+            }
+            let remaining = max_supply - current_supply;
+            if count > remaining {
+                panic!("Not enough supply left: requested {} but {} remaining", count, remaining);
+            }
+        }
+    }
+
+    /// Check `to` against the current phase and count the mint, returning the phase price
+    fn record_phase_mint(
+        env: &Env,
+        to: &Address,
+        payment_token: &Address,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> i128 {
+        let phase =
+            Self::current_mint_phase(env).unwrap_or_else(|| panic!("No active mint phase"));
+        if *payment_token != phase.payment_token {
+            panic!("Payment token not accepted in this phase");
+        }
+        if let Some(root) = phase.merkle_root {
+            let proof = proof.unwrap_or_else(|| panic!("Allowlist proof required"));
+            if !Self::verify_allowlist_proof(env, &root, to, &proof) {
+                panic!("Address is not on the allowlist");
+            }
+        }
+        let key = DataKey::PhaseMinted(phase.start, to.clone());
+        let minted: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        if phase.max_per_wallet > 0 && minted >= phase.max_per_wallet {
+            panic!("Phase mint limit reached: {} / {}", minted, phase.max_per_wallet);
+        }
+        env.storage().persistent().set(&key, &(minted + 1));
+        phase.price
+    }
+
+    /// Verify a Merkle proof that `account` is in the allowlist with root `root`
+    ///
+    /// Leaves are the SHA-256 of the account's XDR-encoded `ScVal::Address`. Each level
+    /// hashes the sorted pair of child nodes, so proofs carry no left/right flags.
+    fn verify_allowlist_proof(
+        env: &Env,
+        root: &BytesN<32>,
+        account: &Address,
+        proof: &Vec<BytesN<32>>,
+    ) -> bool {
+        let mut computed: BytesN<32> =
+            env.crypto().sha256(&account.clone().to_xdr(env)).to_bytes();
+        for node in proof.iter() {
+            let (left, right) = if computed <= node {
+                (computed, node)
+            } else {
+                (node, computed)
+            };
+            let mut pair = Bytes::from(left);
+            pair.append(&Bytes::from(right));
+            computed = env.crypto().sha256(&pair).to_bytes();
+        }
+        computed == *root
+    }
+
+    /// Count a mint to `to`, panicking if it would exceed the per-wallet limit
+    fn record_wallet_mint(env: &Env, to: &Address) {
+        let minted = Self::minted_by(env, to.clone());
+        let max_per_wallet = Self::max_per_wallet(env);
+        if max_per_wallet > 0 && minted >= max_per_wallet {
+            panic!("Wallet mint limit reached: {} / {}", minted, max_per_wallet);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::MintedBy(to.clone()), &(minted + 1));
     }
 
     /// Get the maximum supply from storage
@@ -101,37 +895,52 @@ impl NonFungibleTokenContract {
     /// 
     /// # Arguments
     /// * `to` - The address that will receive the newly minted NFT
+    /// * `payment_token` - The accepted payment token to pay with (see `payment_tokens`)
+    /// * `proof` - Merkle proof that `to` is on the allowlist (allowlist phases only)
     /// 
     /// # Returns
     /// Returns the token ID of the newly minted NFT (sequential, starting from 1)
     /// 
     /// # Note
-    /// Any user can mint NFTs. The function automatically transfers 0.01 XLM (100,000 stroops)
-    /// from the recipient (`to`) to the contract as payment. The recipient must have sufficient XLM balance
-    /// and must authorize the transaction (sign it).
+    /// Any user can mint NFTs. The function automatically transfers `mint_price(payment_token)`
+    /// (0.01 XLM / 100,000 stroops by default for XLM) from the recipient (`to`) to the contract
+    /// as payment. The recipient must have sufficient balance of `payment_token` and must
+    /// authorize the transaction (sign it). When the price is 0, no payment is taken.
+    ///
+    /// When mint phases are configured, minting is only possible during a phase, which sets
+    /// the payment token, price and an extra per-wallet cap, and may require an allowlist proof.
+    /// Fails once a voucher signer is set (use `mint_with_voucher`).
     /// 
     /// If minting fails after payment, the payment will be refunded to the recipient.
     /// In Soroban, if sequential_mint panics, the entire transaction reverts,
     /// so the payment transfer will also be reverted automatically. No explicit refund needed.
-    pub fn mint(env: Env, to: Address) -> u32 {
+    pub fn mint(
+        env: Env,
+        to: Address,
+        payment_token: Address,
+        proof: Option<Vec<BytesN<32>>>,
+    ) -> u32 {
+        // Check-in badge collections can only be minted with vouchers
+        if Self::voucher_signer(&env).is_some() {
+            panic!("Minting requires a check-in voucher");
+        }
+
         // Validate recipient address is not the contract itself (prevents self-minting issues)
         let contract_address = env.current_contract_address();
         if to == contract_address {
             panic!("Cannot mint to contract address");
         }
         
-        // Check supply cap before proceeding
-        let max_supply = Self::get_max_supply(&env);
-        if max_supply > 0 {
-            let current_supply = Self::get_current_supply(&env);
-            if current_supply >= max_supply {
-                panic!("Maximum supply reached: {} / {}", current_supply, max_supply);
-            }
-        }
+        // Check supply cap and per-wallet limit before proceeding
+        Self::require_supply(&env, 1);
+        Self::record_wallet_mint(&env, &to);
         
-        // Get the native asset (XLM) contract address
-        let native_asset_address = Self::get_native_asset_address(&env);
-        let native_token = token::Client::new(&env, &native_asset_address);
+        // Look up the price first so unaccepted payment tokens are rejected before any transfer
+        let mint_cost = if Self::mint_phases(&env).is_empty() {
+            Self::mint_price(&env, payment_token.clone())
+        } else {
+            Self::record_phase_mint(&env, &to, &payment_token, proof)
+        };
         
         // Require authorization from the recipient (they must sign the transaction)
         // This ensures they are paying for the mint
@@ -140,15 +949,17 @@ impl NonFungibleTokenContract {
         // authorizes the transaction, which provides some protection against malicious contracts.
         to.require_auth();
         
-        // Transfer 0.01 XLM (100,000 stroops) from the recipient to the contract as payment
-        // Note: We do not check balance here. native_token.balance(&to) queries the SAC balance,
+        // Transfer the mint price from the recipient to the contract as payment
+        // Note: We do not check balance here. For XLM, balance(&to) queries the SAC balance,
         // which can be 0 for Stellar accounts (G...) that haven't interacted with the SAC yet,
         // even when the account has native XLM. The transfer() call will fail with a proper
         // error if the account has insufficient balance.
-        let mint_cost: i128 = 100_000; // 0.01 XLM in stroops
         
         // Transfer payment first (checks-effects-interactions pattern)
-        native_token.transfer(&to, &contract_address, &mint_cost);
+        // Free mints (price 0) skip the payment entirely
+        if mint_cost > 0 {
+            token::Client::new(&env, &payment_token).transfer(&to, &contract_address, &mint_cost);
+        }
         
         // Mint the NFT to the recipient
         // If this fails, the entire transaction reverts, including the payment transfer
@@ -160,27 +971,27 @@ impl NonFungibleTokenContract {
         token_id
     }
 
-    /// Withdraw XLM from the contract to the owner
+    /// Withdraw collected fees in a token from the contract to the owner
     /// 
     /// # Arguments
-    /// * `amount` - The amount of XLM (in stroops) to withdraw. If None, withdraws all available balance.
+    /// * `token` - The token contract address to withdraw (e.g. XLM, USDC or IRLToken)
+    /// * `amount` - The amount to withdraw in `token`'s smallest unit. If None, withdraws all available balance.
     /// 
     /// # Note
-    /// Only the contract owner can call this function. The function transfers XLM from the contract
+    /// Only the contract owner can call this function. The function transfers `token` from the contract
     /// address to the owner's address. This allows the owner to withdraw minting fees collected by the contract.
-    pub fn withdraw(env: Env, amount: Option<i128>) -> i128 {
+    /// `token` does not have to be a currently accepted payment token.
+    pub fn withdraw(env: Env, token: Address, amount: Option<i128>) -> i128 {
         // Require that the caller is the owner
         let owner = ownable::get_owner(&env).expect("Contract has no owner");
         owner.require_auth();
         
-        // Get the native asset (XLM) contract address
-        let native_asset_address = Self::get_native_asset_address(&env);
-        let native_token = token::Client::new(&env, &native_asset_address);
+        let token_client = token::Client::new(&env, &token);
         
         let contract_address = env.current_contract_address();
         
         // Get the contract's balance
-        let contract_balance = native_token.balance(&contract_address);
+        let contract_balance = token_client.balance(&contract_address);
         
         // Determine how much to withdraw
         let withdraw_amount = match amount {
@@ -198,8 +1009,8 @@ impl NonFungibleTokenContract {
             None => contract_balance, // Withdraw all if no amount specified
         };
         
-        // Transfer XLM from contract to owner
-        native_token.transfer(&contract_address, &owner, &withdraw_amount);
+        // Transfer the token from contract to owner
+        token_client.transfer(&contract_address, &owner, &withdraw_amount);
         
         withdraw_amount
     }
@@ -212,14 +1023,34 @@ impl NonFungibleToken for NonFungibleTokenContract {
 
     #[when_not_paused]
     fn transfer(e: &Env, from: Address, to: Address, token_id: u32) {
+        Self::require_transferable(e);
         Self::ContractType::transfer(e, &from, &to, token_id);
     }
 
     #[when_not_paused]
     fn transfer_from(e: &Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        Self::require_transferable(e);
         Self::ContractType::transfer_from(e, &spender, &from, &to, token_id);
     }
 
+    #[when_not_paused]
+    fn approve(
+        e: &Env,
+        approver: Address,
+        approved: Address,
+        token_id: u32,
+        live_until_ledger: u32,
+    ) {
+        Self::require_transferable(e);
+        Self::ContractType::approve(e, &approver, &approved, token_id, live_until_ledger);
+    }
+
+    #[when_not_paused]
+    fn approve_for_all(e: &Env, owner: Address, operator: Address, live_until_ledger: u32) {
+        Self::require_transferable(e);
+        Self::ContractType::approve_for_all(e, &owner, &operator, live_until_ledger);
+    }
+
     #[when_not_paused]
     fn balance(e: &Env, owner: Address) -> u32 {
         Self::ContractType::balance(e, &owner)
@@ -229,6 +1060,18 @@ impl NonFungibleToken for NonFungibleTokenContract {
     fn owner_of(e: &Env, token_id: u32) -> Address {
         Self::ContractType::owner_of(e, token_id)
     }
+
+    /// Returns the token's own URI if one was set, otherwise the collection base URI.
+    fn token_uri(e: &Env, token_id: u32) -> String {
+        match e.storage().persistent().get(&DataKey::TokenUri(token_id)) {
+            Some(uri) => {
+                // Panics if the token does not exist (e.g. it was burned)
+                Self::ContractType::owner_of(e, token_id);
+                uri
+            }
+            None => Self::ContractType::token_uri(e, token_id),
+        }
+    }
 }
 
 #[contractimpl(contracttrait)]
@@ -251,6 +1094,40 @@ impl NonFungibleEnumerable for NonFungibleTokenContract {}
 #[contractimpl(contracttrait)]
 impl Ownable for NonFungibleTokenContract {}
 
+#[contractimpl]
+impl NonFungibleRoyalties for NonFungibleTokenContract {
+    /// Sets the collection-wide royalty used for tokens without their own.
+    /// `operator` must be the owner and must authorize the call.
+    fn set_default_royalty(e: &Env, receiver: Address, basis_points: u32, operator: Address) {
+        Self::require_owner_operator(e, &operator);
+        Base::set_default_royalty(e, &receiver, basis_points);
+    }
+
+    /// Sets a royalty for one token, overriding the collection default.
+    /// `operator` must be the owner and must authorize the call.
+    fn set_token_royalty(
+        e: &Env,
+        token_id: u32,
+        receiver: Address,
+        basis_points: u32,
+        operator: Address,
+    ) {
+        Self::require_owner_operator(e, &operator);
+        Base::set_token_royalty(e, token_id, &receiver, basis_points);
+    }
+
+    /// Removes a token's own royalty so it falls back to the collection default.
+    /// `operator` must be the owner and must authorize the call.
+    fn remove_token_royalty(e: &Env, token_id: u32, operator: Address) {
+        Self::require_owner_operator(e, &operator);
+        Base::remove_token_royalty(e, token_id);
+    }
+
+    fn royalty_info(e: &Env, token_id: u32, sale_price: i128) -> (Address, i128) {
+        Base::royalty_info(e, token_id, sale_price)
+    }
+}
+
 #[contractimpl]
 impl Pausable for NonFungibleTokenContract {
     fn paused(e: &Env) -> bool {